@@ -0,0 +1,685 @@
+//! Procedural macros for [`cfgenius`](https://docs.rs/cfgenius). You should not depend on this
+//! crate directly; enable the `attr` or `proc-macro` features of `cfgenius` instead.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Includes the annotated item if the provided `cfgenius` predicate is truthy.
+///
+/// This expands to an invocation of `cfgenius::cond!` so `cfgenius` must be accessible under that
+/// name to the crate using the attribute.
+///
+/// If the predicate is followed by `, doc_cfg`, the item is additionally annotated with
+/// `#[cfg_attr(docsrs, doc(cfg(...)))]` whenever the predicate can be expressed as a regular cfg
+/// predicate.
+#[proc_macro_attribute]
+pub fn when(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attr = attr.into_iter().collect::<Vec<_>>();
+    let doc_cfg = strip_option(&mut attr, "doc_cfg");
+
+    if attr.is_empty() {
+        return compile_error(
+            "expected a predicate, e.g. `#[cfgenius::when(cfg(unix))]`",
+            Span::call_site(),
+        );
+    }
+
+    // #[cfg_attr(docsrs, doc(cfg(<cfg>)))] <item>
+    let mut item_tokens = TokenStream::new();
+
+    if doc_cfg {
+        if let Some(cfg) = to_cfg(&attr) {
+            let docsrs = ident("docsrs");
+            let doc = call("doc", [call("cfg", [cfg])]);
+
+            item_tokens.extend([
+                TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+                TokenTree::Group(Group::new(
+                    Delimiter::Bracket,
+                    call("cfg_attr", [docsrs, doc]),
+                )),
+            ]);
+        }
+    }
+
+    item_tokens.extend(item);
+
+    // ::cfgenius::cond! { if <attr> { <item> } }
+    let mut body = ident("if");
+    body.extend(attr);
+    body.extend([TokenTree::Group(Group::new(Delimiter::Brace, item_tokens))]);
+
+    invoke(path(&["cfgenius", "cond"]), body)
+}
+
+/// The procedural entry point of `cfgenius::cond!`, enabled by the `proc-macro` feature.
+///
+/// `cfgenius::cond!` passes its `$crate` path in brackets ahead of the chain so that the expansion
+/// refers to `cfgenius` however the crate using the macro imported it.
+///
+/// Chains whose predicates can all be expressed as regular cfg predicates are lowered into one
+/// `#[cfg(...)]`-gated branch per body. Chains in which a `macro(...)` predicate is repeated within
+/// the predicate of a branch are rewritten to evaluate it only once. Every other input, including
+/// malformed input, is forwarded verbatim to the `macro_rules!` implementation in
+/// `cfgenius::__cond!`.
+#[proc_macro]
+pub fn cond(input: TokenStream) -> TokenStream {
+    let mut input = input.into_iter();
+    let krate = match input.next() {
+        Some(TokenTree::Group(krate)) if krate.delimiter() == Delimiter::Bracket => krate.stream(),
+        _ => {
+            return compile_error(
+                "`cfgenius_macros::cond!` must be invoked through `cfgenius::cond!`",
+                Span::call_site(),
+            )
+        }
+    };
+    let tokens = input.collect::<Vec<_>>();
+
+    match lower_chain(&krate, &tokens).or_else(|| hoist_repeated_macro(&krate, &tokens)) {
+        Some(out) => out,
+        None => invoke(member(&krate, "__cond"), tokens.into_iter().collect()),
+    }
+}
+
+/// Lowers `if <pred> { ... } else if <pred> { ... } else { ... }` into
+/// `#[cfg(<guard>)] $crate::__cond! { @__internal_id ... }` for every branch, where each guard also
+/// excludes the predicates of the branches before it.
+fn lower_chain(krate: &TokenStream, mut tokens: &[TokenTree]) -> Option<TokenStream> {
+    let mut out = TokenStream::new();
+    let mut taken = Vec::new();
+
+    loop {
+        let cfg = match tokens {
+            [TokenTree::Ident(kw), rest @ ..] if kw.to_string() == "if" => {
+                let body_at = find_body(rest)?;
+
+                let cfg = to_cfg_alternatives(&rest[..body_at])?;
+                tokens = &rest[body_at..];
+                Some(cfg)
+            }
+            _ if taken.is_empty() => return None,
+            _ => None,
+        };
+
+        let [TokenTree::Group(body), rest @ ..] = tokens else {
+            return None;
+        };
+
+        let guard = match (&cfg, taken.is_empty()) {
+            (Some(cfg), true) => cfg.clone(),
+            (Some(cfg), false) => call(
+                "all",
+                [call("not", [call("any", taken.clone())]), cfg.clone()],
+            ),
+            (None, _) => call("not", [call("any", taken.clone())]),
+        };
+
+        let mut body_tokens = TokenStream::from_iter([
+            TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+            TokenTree::Ident(Ident::new("__internal_id", Span::call_site())),
+        ]);
+        body_tokens.extend(body.stream());
+
+        out.extend([
+            TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+            TokenTree::Group(Group::new(Delimiter::Bracket, call("cfg", [guard]))),
+        ]);
+        out.extend(invoke(member(krate, "__cond"), body_tokens));
+
+        let Some(cfg) = cfg else {
+            // The `else` branch must end the chain.
+            return rest.is_empty().then_some(out);
+        };
+        taken.push(cfg);
+
+        match rest {
+            [] => return Some(out),
+            [TokenTree::Ident(kw), rest @ ..] if kw.to_string() == "else" => tokens = rest,
+            _ => return None,
+        }
+    }
+}
+
+/// Rewrites the first branch whose predicate contains the same `macro(...)` predicate more than once
+/// so that the variable is only expanded once:
+///
+/// ```plain_text
+/// <earlier branches> else {
+///     $crate::__cond! {
+///         if macro(<variable>) {
+///             $crate::cond! { if <predicate with `true()`> { ... } <later branches> }
+///         } else {
+///             $crate::cond! { if <predicate with `false()`> { ... } <later branches> }
+///         }
+///     }
+/// }
+/// ```
+///
+/// Since this evaluates the variable before the rest of the predicate, the rewrite only applies if
+/// one of its appearances would be evaluated regardless of the other operands. Otherwise, operands
+/// such as `all(macro(is_defined => name), macro(name))` could no longer guard its evaluation.
+fn hoist_repeated_macro(krate: &TokenStream, tokens: &[TokenTree]) -> Option<TokenStream> {
+    let mut rest = tokens;
+
+    loop {
+        let [TokenTree::Ident(kw), branch @ ..] = rest else {
+            return None;
+        };
+
+        if kw.to_string() != "if" {
+            return None;
+        }
+
+        let body_at = find_body(branch)?;
+
+        let pred = &branch[..body_at];
+        let after_pred = &branch[body_at..];
+
+        if let Some(variable) = repeated_macro(pred) {
+            let key = variable.to_string();
+            let resolved = |value: bool| {
+                let mut chain = ident("if");
+                chain.extend(substitute_macro(pred, &key, value));
+                chain.extend(after_pred.iter().cloned());
+                invoke(member(krate, "cond"), chain)
+            };
+
+            let mut hoisted = ident("if");
+            hoisted.extend(call("macro", [variable]));
+            hoisted.extend([TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                resolved(true),
+            ))]);
+            hoisted.extend(ident("else"));
+            hoisted.extend([TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                resolved(false),
+            ))]);
+            let hoisted = invoke(member(krate, "__cond"), hoisted);
+
+            let earlier = &tokens[..tokens.len() - rest.len()];
+
+            if earlier.is_empty() {
+                return Some(hoisted);
+            }
+
+            let mut chain = earlier.iter().cloned().collect::<TokenStream>();
+            chain.extend([TokenTree::Group(Group::new(Delimiter::Brace, hoisted))]);
+            return Some(invoke(member(krate, "cond"), chain));
+        }
+
+        match &after_pred[1..] {
+            [TokenTree::Ident(kw), next @ ..] if kw.to_string() == "else" => rest = next,
+            _ => return None,
+        }
+    }
+}
+
+/// Finds the arguments of the first `macro(...)` predicate which appears more than once in the
+/// `|`-separated predicates of a branch and at least once where it is always evaluated.
+fn repeated_macro(pred: &[TokenTree]) -> Option<TokenStream> {
+    if matches!(pred.last(), Some(TokenTree::Punct(punct)) if punct.as_char() == '|') {
+        return None;
+    }
+
+    let mut appearances = Vec::new();
+    let mut always = true;
+
+    // Alternatives are lowered to `any(...)` and evaluated like its operands.
+    for alternative in split_on(pred.iter().cloned().collect(), '|') {
+        collect_macros(&alternative, always, &mut appearances);
+        always &= is_static(&alternative);
+    }
+
+    appearances
+        .iter()
+        .find(|(key, _, always)| {
+            *always
+                && appearances
+                    .iter()
+                    .filter(|(other, _, _)| other == key)
+                    .count()
+                    > 1
+        })
+        .map(|(_, variable, _)| variable.clone())
+}
+
+/// Collects the arguments of every `macro(...)` predicate, without any leading `!`, keyed by their
+/// textual representation. `always` records whether the predicate is evaluated regardless of the
+/// operands around it, which is only the case for the operands of `all` and `any` which aren't
+/// preceded by an operand which might short-circuit the group.
+fn collect_macros(pred: &[TokenTree], always: bool, out: &mut Vec<(String, TokenStream, bool)>) {
+    match pred {
+        [TokenTree::Ident(name), TokenTree::Group(args)]
+            if args.delimiter() == Delimiter::Parenthesis =>
+        {
+            match name.to_string().as_str() {
+                "macro" => {
+                    let variable = strip_negation(args.stream());
+                    let key = variable.to_string();
+
+                    // `$crate` may refer to a different crate depending on where it came from.
+                    if !key.contains("$crate") {
+                        out.push((key, variable, always));
+                    }
+                }
+                "all_spliced" | "any_spliced" => {}
+                "all" | "any" => {
+                    let mut always = always;
+
+                    for operand in split_on(args.stream(), ',') {
+                        collect_macros(&operand, always, out);
+                        always &= is_static(&operand);
+                    }
+                }
+                "not" => {
+                    collect_macros(&args.stream().into_iter().collect::<Vec<_>>(), always, out)
+                }
+                _ => {
+                    for operand in split_on(args.stream(), ',') {
+                        collect_macros(&operand, false, out);
+                    }
+                }
+            }
+        }
+        [TokenTree::Ident(name), TokenTree::Group(operands)]
+            if operands.delimiter() == Delimiter::Brace
+                && matches!(name.to_string().as_str(), "all" | "any") =>
+        {
+            for operand in split_on(operands.stream(), ';') {
+                collect_macros(&operand, false, out);
+            }
+        }
+        [TokenTree::Group(inner)] if inner.delimiter() == Delimiter::Parenthesis => {
+            collect_macros(&inner.stream().into_iter().collect::<Vec<_>>(), always, out);
+        }
+        _ => {}
+    }
+}
+
+/// Whether the predicate is folded into a cfg predicate without ever short-circuiting the group
+/// containing it.
+fn is_static(pred: &[TokenTree]) -> bool {
+    match pred {
+        [TokenTree::Ident(name), TokenTree::Group(args)]
+            if args.delimiter() == Delimiter::Parenthesis =>
+        {
+            match name.to_string().as_str() {
+                "cfg" | "cfg_not" | "cfg_eq" | "feature" | "target_os" | "rustc_at_least" => true,
+                "all" | "any" | "not" => split_on(args.stream(), ',')
+                    .iter()
+                    .all(|operand| is_static(operand)),
+                _ => false,
+            }
+        }
+        [TokenTree::Group(inner)] if inner.delimiter() == Delimiter::Parenthesis => {
+            is_static(&inner.stream().into_iter().collect::<Vec<_>>())
+        }
+        _ => false,
+    }
+}
+
+/// Replaces every `macro(<variable>)` predicate with `true()` or `false()` according to `value`,
+/// taking negated `macro(!<variable>)` predicates into account. The arguments of predicates which
+/// are passed to other macros are left untouched.
+fn substitute_macro(pred: &[TokenTree], variable: &str, value: bool) -> TokenStream {
+    let mut out = TokenStream::new();
+    let mut i = 0;
+
+    while i < pred.len() {
+        match (&pred[i], pred.get(i + 1)) {
+            (TokenTree::Ident(name), Some(TokenTree::Group(args)))
+                if args.delimiter() == Delimiter::Parenthesis
+                    && matches!(
+                        name.to_string().as_str(),
+                        "macro" | "all_spliced" | "any_spliced"
+                    ) =>
+            {
+                if name.to_string() == "macro"
+                    && strip_negation(args.stream()).to_string() == variable
+                {
+                    let value = value != is_negated(&args.stream());
+                    out.extend(call(if value { "true" } else { "false" }, []));
+                } else {
+                    out.extend([pred[i].clone(), pred[i + 1].clone()]);
+                }
+
+                i += 2;
+            }
+            (TokenTree::Group(group), _) => {
+                let inner = group.stream().into_iter().collect::<Vec<_>>();
+                let mut substituted =
+                    Group::new(group.delimiter(), substitute_macro(&inner, variable, value));
+                substituted.set_span(group.span());
+                out.extend([TokenTree::Group(substituted)]);
+                i += 1;
+            }
+            (tt, _) => {
+                out.extend([tt.clone()]);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+fn is_negated(args: &TokenStream) -> bool {
+    matches!(
+        args.clone().into_iter().next(),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '!',
+    )
+}
+
+fn strip_negation(args: TokenStream) -> TokenStream {
+    let negated = is_negated(&args);
+    args.into_iter().skip(usize::from(negated)).collect()
+}
+
+/// Translates a `|`-separated list of predicates into an equivalent cfg predicate, if there is one.
+fn to_cfg_alternatives(pred: &[TokenTree]) -> Option<TokenStream> {
+    // Unlike argument lists, alternatives don't accept a trailing separator.
+    if matches!(pred.last(), Some(TokenTree::Punct(punct)) if punct.as_char() == '|') {
+        return None;
+    }
+
+    let mut alternatives = split_on(pred.iter().cloned().collect(), '|')
+        .iter()
+        .map(|pred| to_cfg(pred))
+        .collect::<Option<Vec<_>>>()?;
+
+    match alternatives.len() {
+        0 => None,
+        1 => alternatives.pop(),
+        _ => Some(call("any", alternatives)),
+    }
+}
+
+/// Removes a trailing `, <name>` option from the attribute input, returning whether it was present.
+fn strip_option(attr: &mut Vec<TokenTree>, name: &str) -> bool {
+    match attr.as_slice() {
+        [.., TokenTree::Punct(comma), TokenTree::Ident(option)]
+            if comma.as_char() == ',' && option.to_string() == name =>
+        {
+            attr.truncate(attr.len() - 2);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Translates a `cfgenius` predicate into an equivalent cfg predicate, if there is one.
+fn to_cfg(pred: &[TokenTree]) -> Option<TokenStream> {
+    let (name, args) = match pred {
+        [TokenTree::Ident(name), TokenTree::Group(args)]
+            if args.delimiter() == Delimiter::Parenthesis =>
+        {
+            (name.to_string(), args.stream())
+        }
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Parenthesis => {
+            return to_cfg(&group.stream().into_iter().collect::<Vec<_>>());
+        }
+        [TokenTree::Ident(name), TokenTree::Group(block)]
+            if block.delimiter() == Delimiter::Brace
+                && matches!(name.to_string().as_str(), "all" | "any") =>
+        {
+            let preds = split_on(block.stream(), ';')
+                .iter()
+                .map(|pred| to_cfg(pred))
+                .collect::<Option<Vec<_>>>()?;
+
+            return Some(call(&name.to_string(), preds));
+        }
+        _ => return None,
+    };
+
+    let list = split_on(args.clone(), ',');
+    let preds = || {
+        list.iter()
+            .map(|pred| to_cfg(pred))
+            .collect::<Option<Vec<_>>>()
+    };
+
+    Some(match (name.as_str(), list.as_slice()) {
+        ("cfg", [_]) => args,
+        ("cfg_not", [_]) => negate(args),
+        ("true", []) => call("all", []),
+        ("false", []) => call("any", []),
+        ("not", [pred]) => negate(to_cfg(pred)?),
+        ("all" | "any", _) => call(&name, preds()?),
+        ("nor" | "none_of", _) => call("not", [call("any", preds()?)]),
+        ("nand", _) => call("not", [call("all", preds()?)]),
+        ("xor", [left, right]) => {
+            let (left, right) = (to_cfg(left)?, to_cfg(right)?);
+
+            call(
+                "any",
+                [
+                    call("all", [left.clone(), negate(right.clone())]),
+                    call("all", [negate(left), right]),
+                ],
+            )
+        }
+        ("iff", [left, right]) => {
+            let (left, right) = (to_cfg(left)?, to_cfg(right)?);
+
+            call(
+                "any",
+                [
+                    call("all", [left.clone(), right.clone()]),
+                    call("not", [call("any", [left, right])]),
+                ],
+            )
+        }
+        ("implies", [left, right]) => call("any", [negate(to_cfg(left)?), to_cfg(right)?]),
+        ("cfg_eq", [key, value])
+            if matches!(key.as_slice(), [TokenTree::Ident(_)]) && is_literal(value) =>
+        {
+            key_value(key.iter().cloned().collect(), value)
+        }
+        ("feature", names) if !names.is_empty() && names.iter().all(|name| is_literal(name)) => {
+            call(
+                "all",
+                names.iter().map(|name| key_value(ident("feature"), name)),
+            )
+        }
+        ("target_os", names) if !names.is_empty() && names.iter().all(|name| is_literal(name)) => {
+            call(
+                "any",
+                names.iter().map(|name| key_value(ident("target_os"), name)),
+            )
+        }
+        ("rustc_at_least", [version]) if is_literal(version) => {
+            key_value(ident("cfgenius_rustc_at_least"), version)
+        }
+        ("between", [low, high, key]) if matches!(key.as_slice(), [TokenTree::Ident(_)]) => {
+            let (low, high) = (numeral(low)?, numeral(high)?);
+
+            call(
+                "any",
+                (low..=high).map(|value| {
+                    key_value(
+                        key.iter().cloned().collect(),
+                        &[TokenTree::Literal(Literal::string(&value.to_string()))],
+                    )
+                }),
+            )
+        }
+        ("ptr_width_eq", [width]) => ptr_widths(width)?.into_iter().next()?,
+        ("ptr_width_at_least", [width]) => call("any", ptr_widths(width)?),
+        _ => return None,
+    })
+}
+
+/// Produces the `target_pointer_width` predicates for every supported width greater than or equal
+/// to the provided one. Mirrors `@__internal_ptr_widths` in `cfgenius`.
+fn ptr_widths(width: &[TokenTree]) -> Option<Vec<TokenStream>> {
+    let [TokenTree::Literal(width)] = width else {
+        return None;
+    };
+
+    let widths: &[&str] = match width.to_string().as_str() {
+        "16" => &["16", "32", "64"],
+        "32" => &["32", "64"],
+        "64" => &["64"],
+        "128" => &["128"],
+        _ => return None,
+    };
+
+    Some(
+        widths
+            .iter()
+            .map(|width| {
+                key_value(
+                    ident("target_pointer_width"),
+                    &[TokenTree::Literal(Literal::string(width))],
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Parses the bounds of `between`. Mirrors `@__internal_numerals` in `cfgenius`.
+fn numeral(value: &[TokenTree]) -> Option<u8> {
+    let [TokenTree::Literal(value)] = value else {
+        return None;
+    };
+
+    value.to_string().parse().ok().filter(|&value| value <= 32)
+}
+
+fn split_on(stream: TokenStream, separator: char) -> Vec<Vec<TokenTree>> {
+    let mut list = vec![Vec::new()];
+
+    for tt in stream {
+        match tt {
+            TokenTree::Punct(punct) if punct.as_char() == separator => list.push(Vec::new()),
+            tt => list.last_mut().unwrap().push(tt),
+        }
+    }
+
+    if list.last().is_some_and(Vec::is_empty) {
+        list.pop();
+    }
+
+    list
+}
+
+/// Finds the body of a branch given the tokens following its `if`. The body is the first brace
+/// group which ends the chain or is followed by `else`. Earlier brace groups belong to the
+/// `all { ... }` and `any { ... }` block forms.
+fn find_body(branch: &[TokenTree]) -> Option<usize> {
+    (0..branch.len()).find(|&i| {
+        is_group(&branch[i], Delimiter::Brace)
+            && match branch.get(i + 1) {
+                Some(tt) => is_ident(tt, "else"),
+                None => true,
+            }
+    })
+}
+
+fn is_ident(tt: &TokenTree, name: &str) -> bool {
+    matches!(tt, TokenTree::Ident(ident) if ident.to_string() == name)
+}
+
+fn is_group(tt: &TokenTree, delimiter: Delimiter) -> bool {
+    matches!(tt, TokenTree::Group(group) if group.delimiter() == delimiter)
+}
+
+fn is_literal(tokens: &[TokenTree]) -> bool {
+    matches!(tokens, [TokenTree::Literal(_)])
+}
+
+fn ident(name: &str) -> TokenStream {
+    TokenStream::from(TokenTree::Ident(Ident::new(name, Span::call_site())))
+}
+
+fn call(name: &str, args: impl IntoIterator<Item = TokenStream>) -> TokenStream {
+    let mut inner = TokenStream::new();
+
+    for (i, arg) in args.into_iter().enumerate() {
+        if i > 0 {
+            inner.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        inner.extend(arg);
+    }
+
+    let mut out = ident(name);
+    out.extend([TokenTree::Group(Group::new(Delimiter::Parenthesis, inner))]);
+    out
+}
+
+fn negate(cfg: TokenStream) -> TokenStream {
+    // Cancel out double negations rather than emitting `not(not(...))`.
+    let tokens = cfg.clone().into_iter().collect::<Vec<_>>();
+
+    match tokens.as_slice() {
+        [TokenTree::Ident(not), TokenTree::Group(group)]
+            if not.to_string() == "not"
+                && group.delimiter() == Delimiter::Parenthesis
+                && split_on(group.stream(), ',').len() == 1 =>
+        {
+            group.stream()
+        }
+        _ => call("not", [cfg]),
+    }
+}
+
+fn key_value(key: TokenStream, value: &[TokenTree]) -> TokenStream {
+    let mut out = key;
+    out.extend([TokenTree::Punct(Punct::new('=', Spacing::Alone))]);
+    out.extend(value.iter().cloned());
+    out
+}
+
+fn invoke(macro_path: TokenStream, body: TokenStream) -> TokenStream {
+    let mut out = macro_path;
+    out.extend([
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Brace, body)),
+    ]);
+    out
+}
+
+fn path(segments: &[&str]) -> TokenStream {
+    let mut out = TokenStream::new();
+
+    for segment in segments {
+        out.extend([
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new(segment, Span::call_site())),
+        ]);
+    }
+
+    out
+}
+
+/// Appends `::<name>` to the path of a crate.
+fn member(krate: &TokenStream, name: &str) -> TokenStream {
+    let mut out = krate.clone();
+    out.extend([
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new(name, Span::call_site())),
+    ]);
+    out
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut out = path(&["core", "compile_error"]);
+    out.extend([
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Brace,
+            TokenStream::from(TokenTree::Literal(Literal::string(message))),
+        )),
+    ]);
+
+    out.into_iter()
+        .map(|mut tt| {
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}
@@ -0,0 +1,91 @@
+//! Checks that everything `cfgenius` expands to compiles in a `#![no_std]` crate.
+//!
+//! Since `std` is not in scope here, any expansion referring to it fails to build this crate.
+//! Every check is a constant assertion so building the crate also checks that the predicates
+//! resolve correctly. Enable the `proc-macro` feature to check the procedural implementation of
+//! `cond!` as well.
+
+#![no_std]
+
+cfgenius::define! {
+    pub is_enabled = true();
+    pub is_disabled as IS_DISABLED = not(macro(is_enabled));
+    pub(crate) has_target_os($os) = cfg(target_os = $os);
+    pub(crate) is_enabled_alias = alias is_enabled;
+}
+
+cfgenius::define_const! {
+    pub IS_ENABLED = macro(is_enabled);
+    IS_NEITHER = nor(macro(is_enabled), macro(is_disabled));
+}
+
+const _: () = assert!(IS_ENABLED && !IS_NEITHER);
+
+cfgenius::cond! {
+    if all(macro(is_enabled), cfg(all())) {
+        pub const SELECTED: u32 = 1;
+    } else if macro(is_disabled) {
+        pub const SELECTED: u32 = 2;
+    } else {
+        pub const SELECTED: u32 = 3;
+    }
+}
+
+cfgenius::cond_variants! {
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum Backend {
+        Portable,
+        cond! {
+            if macro(is_enabled) {
+                Native,
+            }
+        }
+    }
+}
+
+cfgenius::cond_fields! {
+    #[derive(Debug, Default)]
+    pub struct Config {
+        pub level: u8,
+        cond! {
+            if macro(is_disabled) {
+                pub verbose: bool,
+            }
+        }
+    }
+}
+
+#[cfgenius::when(macro(is_enabled))]
+pub fn backend() -> Backend {
+    cfgenius::cond_expr!(if macro(is_enabled) => Backend::Native else Backend::Portable)
+}
+
+pub fn default_config() -> Config {
+    Config { level: cfgenius::cond_expr!(macro(is_enabled_alias) ? 1 : 0) }
+}
+
+pub const TOTAL: u32 = cfgenius::cond_expr_total! {
+    if macro(is_disabled) {
+        0
+    } else if any(cfg(any()), macro(has_target_os => "none")) {
+        1
+    } else {
+        2
+    }
+};
+
+pub type Word = cfgenius::cond_ty!(if ptr_width_eq(64) { u64 } else { u32 });
+
+const _: () = {
+    assert!(SELECTED == 1);
+    assert!(!IS_DISABLED);
+    assert!(TOTAL == cfgenius::cond_expr!(cfg(target_os = "none") => 1, 2));
+    assert!(cfgenius::cond_expr!(all(true(), not(false()), macro(is_enabled))));
+    assert!(cfgenius::cond_expr!(cfg(all()) => true, false));
+    assert!(cfgenius::cond_expr!(at_least(2, true(), macro(is_enabled), false())));
+    assert!(cfgenius::cond_expr!(: u8, if true() => 1 else 2) == 1);
+    assert!(core::mem::size_of::<Word>() == cfgenius::cond_expr!(ptr_width_eq(64) => 8, 4));
+};
+
+cfgenius::static_assert_cfg!(any(macro(is_enabled), cfg(any())));
+cfgenius::warn_cfg!(macro(is_disabled), "unreachable");
@@ -0,0 +1,586 @@
+//! Evaluation of `cfgenius` predicates from build scripts.
+//!
+//! Build scripts are compiled for the host rather than the target so the predicate macros of this
+//! crate would resolve against the wrong configuration. Instead, this module parses predicates
+//! written in the usual syntax at runtime and evaluates them against the target configuration
+//! which Cargo provides to build scripts through the `CARGO_CFG_<KEY>` and `CARGO_FEATURE_<NAME>`
+//! environment variables.
+//!
+//! Every predicate which is ultimately resolved through cfgs is supported. Predicates which depend
+//! on macros, i.e. `macro(...)`, `all_spliced(...)`, and `any_spliced(...)`, cannot be evaluated
+//! outside of macro expansion and are rejected. Likewise, the `test` cfg is never visible to build
+//! scripts and always resolves to false.
+//!
+//! Predicates can also be checked against other targets than the one being built, e.g. to ensure
+//! in CI that a predicate holds for every supported target, using
+//! [`assert_predicate_over_targets`].
+//!
+//! ## Example
+//!
+//! ```no_run
+//! // In `build.rs`...
+//! fn main() -> Result<(), cfgenius::build::Error> {
+//!     cfgenius::build::emit_cfg(
+//!         "has_fast_atomics",
+//!         r#"all(ptr_width_at_least(32), any(target_os("linux", "android"), cfg(windows)))"#,
+//!     )?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{env, fmt, prelude::rust_2021::*, println, process::Command};
+
+/// An error produced while parsing or evaluating a predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Evaluates a predicate against the target configuration of the crate being built.
+///
+/// ```
+/// // Cargo sets these variables before running the build script.
+/// std::env::set_var("CARGO_CFG_TARGET_OS", "linux");
+/// std::env::set_var("CARGO_CFG_UNIX", "");
+/// std::env::set_var("CARGO_FEATURE_SERDE_JSON", "1");
+///
+/// use cfgenius::build::eval;
+///
+/// assert_eq!(eval(r#"all(cfg(unix), target_os("linux", "android"))"#), Ok(true));
+/// assert_eq!(eval(r#"feature("serde-json")"#), Ok(true));
+/// assert_eq!(eval("any { cfg(windows); (not(cfg(unix))) }"), Ok(false));
+/// assert_eq!(eval(r#"cfg(any(windows, target_os = "macos"))"#), Ok(false));
+/// assert_eq!(eval("all(cfg_not(windows), cfg_not(not(unix)))"), Ok(true));
+///
+/// for predicate in ["macro(is_supported)", "macro(!x)", "macro(a::b => c)"] {
+///     assert_eq!(
+///         eval(predicate).unwrap_err().to_string(),
+///         "`macro` predicates depend on macros and cannot be evaluated in build scripts",
+///     );
+/// }
+/// ```
+pub fn eval(predicate: &str) -> Result<bool, Error> {
+    eval_pred(&parse(predicate)?, &Cfgs::Env)
+}
+
+/// Evaluates a predicate against each of the provided target triples and fails with the list of
+/// targets for which it is falsy.
+///
+/// The cfgs of each target are queried by running the compiler Cargo provides in the `RUSTC`
+/// environment variable with `--print cfg --target <triple>`, which works for every target known to
+/// the compiler, even if its standard library isn't installed. This reflects the default
+/// configuration of the target so cfgs which depend on how the crate is built, e.g.
+/// `debug_assertions`, `panic`, and `target_feature`s enabled through `RUSTFLAGS`, may differ from
+/// an actual build, and custom cfgs set by build scripts are never visible. Features are still read
+/// from the environment of the build script since they don't depend on the target. If the compiler
+/// fails to print the cfgs of a target, e.g. because it doesn't know it, its error is returned.
+///
+/// As with [`eval`], `macro(...)`, `all_spliced(...)`, and `any_spliced(...)` predicates are
+/// rejected since they can only be resolved by expanding macros for the actual target. Checking
+/// the cfg predicates of a variable therefore requires passing them to this function separately.
+///
+/// ```
+/// use cfgenius::build::assert_predicate_over_targets;
+///
+/// let targets = ["x86_64-unknown-linux-gnu", "aarch64-apple-darwin", "x86_64-pc-windows-msvc"];
+///
+/// assert_predicate_over_targets(&targets, "ptr_width_eq(64)").unwrap();
+/// assert_predicate_over_targets(&targets, "any(cfg(unix), cfg(windows))").unwrap();
+///
+/// let err = assert_predicate_over_targets(&targets, r#"target_os("linux", "macos")"#).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     r#"`target_os("linux", "macos")` is falsy for the targets `x86_64-pc-windows-msvc`"#,
+/// );
+/// assert_eq!(
+///     assert_predicate_over_targets(&targets, "macro(a::b => c)").unwrap_err().to_string(),
+///     "`macro` predicates depend on macros and cannot be evaluated in build scripts",
+/// );
+///
+/// let err = assert_predicate_over_targets(&["not-a-target"], "true()").unwrap_err();
+/// assert!(err.to_string().starts_with("failed to query the cfgs of `not-a-target`: error"));
+/// ```
+pub fn assert_predicate_over_targets(targets: &[&str], predicate: &str) -> Result<(), Error> {
+    let term = parse(predicate)?;
+    let mut falsy = Vec::new();
+
+    for &target in targets {
+        if !eval_pred(&term, &target_cfgs(target)?)? {
+            falsy.push(format!("`{target}`"));
+        }
+    }
+
+    if falsy.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::new(format!(
+            "`{predicate}` is falsy for the targets {}",
+            falsy.join(", ")
+        )))
+    }
+}
+
+/// Evaluates a predicate and enables the custom cfg `name` for the crate being built if it is
+/// truthy.
+///
+/// This prints the `cargo::rustc-check-cfg` instruction declaring the cfg regardless of the result
+/// of the predicate so that checking the cfg in the crate does not emit `unexpected_cfgs`
+/// warnings. The result of the predicate is returned.
+pub fn emit_cfg(name: &str, predicate: &str) -> Result<bool, Error> {
+    let truthy = eval(predicate)?;
+
+    println!("cargo::rustc-check-cfg=cfg({name})");
+
+    if truthy {
+        println!("cargo::rustc-cfg={name}");
+    }
+
+    Ok(truthy)
+}
+
+/// Sets the `cfgenius_rustc_at_least` cfg checked by the `rustc_at_least("<major>.<minor>")`
+/// predicate for the crate being built.
+///
+/// The cfg is set to every minor version of the current major version up to and including the
+/// version of the compiler, which is determined by running the compiler Cargo provides in the
+/// `RUSTC` environment variable with `--version`.
+pub fn emit_rustc_version() -> Result<(), Error> {
+    let (major, minor) = rustc_version()?;
+
+    println!("cargo::rustc-check-cfg=cfg(cfgenius_rustc_at_least, values(any()))");
+
+    for minor in 0..=minor {
+        println!("cargo::rustc-cfg=cfgenius_rustc_at_least=\"{major}.{minor}\"");
+    }
+
+    Ok(())
+}
+
+fn rustc() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string())
+}
+
+fn rustc_version() -> Result<(u32, u32), Error> {
+    let rustc = rustc();
+    let output = Command::new(&rustc)
+        .arg("--version")
+        .output()
+        .map_err(|err| Error::new(format!("failed to run `{rustc} --version`: {err}")))?;
+
+    if !output.status.success() {
+        return Err(Error::new(format!(
+            "`{rustc} --version` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    // e.g. `rustc 1.80.1 (3f5fd8dd4 2024-08-06)`
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split_whitespace()
+        .nth(1)
+        .and_then(|version| parse_version(version.split('-').next()?))
+        .ok_or_else(|| {
+            Error::new(format!(
+                "unexpected output from `{rustc} --version`: {stdout}"
+            ))
+        })
+}
+
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+fn target_cfgs(target: &str) -> Result<Cfgs, Error> {
+    let rustc = rustc();
+    let output = Command::new(&rustc)
+        .args(["--print", "cfg", "--target", target])
+        .output()
+        .map_err(|err| Error::new(format!("failed to run `{rustc} --print cfg`: {err}")))?;
+
+    if !output.status.success() {
+        return Err(Error::new(format!(
+            "failed to query the cfgs of `{target}`: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    // e.g. `unix` or `target_os="linux"`, with one line per value of multi-valued cfgs.
+    let cfgs = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) => (key.to_string(), Some(value.trim_matches('"').to_string())),
+            None => (line.to_string(), None),
+        })
+        .collect();
+
+    Ok(Cfgs::Printed(cfgs))
+}
+
+// === Parsing === //
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(u32),
+    Punct(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Call(String, Vec<Term>),
+    Ident(String),
+    KeyValue(String, String),
+    Str(String),
+    Int(u32),
+}
+
+fn parse(predicate: &str) -> Result<Term, Error> {
+    let mut parser = Parser::new(predicate)?;
+    let term = parser.term()?;
+    parser.finish()?;
+    Ok(term)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    cursor: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, Error> {
+        let mut tokens = Vec::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_alphabetic() || c == '_' {
+                let mut ident = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    ident.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            } else if c.is_ascii_digit() {
+                let mut digits = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    digits.push(c);
+                    chars.next();
+                }
+                let value = digits
+                    .parse()
+                    .map_err(|_| Error::new(format!("integer literal `{digits}` is too large")))?;
+                tokens.push(Token::Int(value));
+            } else if c == '"' {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => value.push(c),
+                            _ => return Err(Error::new("unsupported escape in string literal")),
+                        },
+                        Some(c) => value.push(c),
+                        None => return Err(Error::new("unterminated string literal")),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            } else if c.is_ascii_punctuation() {
+                chars.next();
+                tokens.push(Token::Punct(c));
+            } else {
+                return Err(Error::new(format!("unexpected character `{c}`")));
+            }
+        }
+
+        Ok(Self { tokens, cursor: 0 })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.cursor).cloned();
+        self.cursor += 1;
+        token
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.cursor += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn finish(&self) -> Result<(), Error> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(Error::new(format!(
+                "unexpected {} after predicate",
+                describe(token)
+            ))),
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, Error> {
+        match self.next() {
+            // The arguments of these predicates are paths and macro arguments rather than
+            // predicates, so they are rejected before their arguments are parsed.
+            Some(Token::Ident(name))
+                if matches!(name.as_str(), "macro" | "all_spliced" | "any_spliced")
+                    && matches!(self.peek(), Some(Token::Punct('(' | '{'))) =>
+            {
+                Err(Error::new(format!(
+                    "`{name}` predicates depend on macros and cannot be evaluated in build scripts"
+                )))
+            }
+            Some(Token::Ident(name)) => {
+                if self.eat('(') {
+                    Ok(Term::Call(name, self.list(',', ')')?))
+                } else if self.eat('{') {
+                    Ok(Term::Call(name, self.list(';', '}')?))
+                } else if self.eat('=') {
+                    match self.next() {
+                        Some(Token::Str(value)) => Ok(Term::KeyValue(name, value)),
+                        _ => Err(Error::new(format!(
+                            "expected a string literal after `{name} =`"
+                        ))),
+                    }
+                } else {
+                    Ok(Term::Ident(name))
+                }
+            }
+            Some(Token::Punct('(')) => {
+                let term = self.term()?;
+                if self.eat(')') {
+                    Ok(term)
+                } else {
+                    Err(Error::new("expected `)`"))
+                }
+            }
+            Some(Token::Str(value)) => Ok(Term::Str(value)),
+            Some(Token::Int(value)) => Ok(Term::Int(value)),
+            Some(token) => Err(Error::new(format!("unexpected {}", describe(&token)))),
+            None => Err(Error::new("unexpected end of predicate")),
+        }
+    }
+
+    fn list(&mut self, separator: char, close: char) -> Result<Vec<Term>, Error> {
+        let mut terms = Vec::new();
+
+        loop {
+            if self.eat(close) {
+                return Ok(terms);
+            }
+
+            terms.push(self.term()?);
+
+            if !self.eat(separator) {
+                return if self.eat(close) {
+                    Ok(terms)
+                } else {
+                    Err(Error::new(format!("expected `{separator}` or `{close}`")))
+                };
+            }
+        }
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Ident(ident) => format!("`{ident}`"),
+        Token::Str(value) => format!("`{value:?}`"),
+        Token::Int(value) => format!("`{value}`"),
+        Token::Punct(punct) => format!("`{punct}`"),
+    }
+}
+
+// === Evaluation === //
+
+/// The cfgs a predicate is evaluated against.
+enum Cfgs {
+    /// The cfgs of the target being built, which Cargo provides through environment variables.
+    Env,
+    /// The cfgs of another target, as printed by `rustc --print cfg`.
+    Printed(Vec<(String, Option<String>)>),
+}
+
+fn eval_pred(term: &Term, cfgs: &Cfgs) -> Result<bool, Error> {
+    let Term::Call(name, args) = term else {
+        return Err(Error::new("expected a predicate"));
+    };
+
+    let name = name.as_str();
+
+    Ok(match (name, args.as_slice()) {
+        ("true", []) => true,
+        ("false", []) => false,
+        ("cfg", [cfg]) => eval_cfg(cfg, cfgs)?,
+        ("cfg_not", [cfg]) => !eval_cfg(cfg, cfgs)?,
+        ("not", [pred]) => !eval_pred(pred, cfgs)?,
+        ("all", preds) => count(preds, cfgs)? == preds.len(),
+        ("any", preds) => count(preds, cfgs)? > 0,
+        ("nor" | "none_of", preds) => count(preds, cfgs)? == 0,
+        ("nand", preds) => count(preds, cfgs)? != preds.len(),
+        ("xor", [left, right]) => eval_pred(left, cfgs)? != eval_pred(right, cfgs)?,
+        ("iff", [left, right]) => eval_pred(left, cfgs)? == eval_pred(right, cfgs)?,
+        ("implies", [left, right]) => !eval_pred(left, cfgs)? || eval_pred(right, cfgs)?,
+        ("cfg_eq", [Term::Ident(key), Term::Str(value)]) => has_cfg(cfgs, key, Some(value)),
+        ("feature", names) if !names.is_empty() => {
+            strings(name, names)?.all(|name| has_cfg(cfgs, "feature", Some(name)))
+        }
+        ("target_os", names) if !names.is_empty() => {
+            strings(name, names)?.any(|name| has_cfg(cfgs, "target_os", Some(name)))
+        }
+        ("ptr_width_eq", [width]) => ptr_width(width)? == target_ptr_width(cfgs)?,
+        ("ptr_width_at_least", [width]) => ptr_width(width)? <= target_ptr_width(cfgs)?,
+        ("rustc_at_least", [Term::Str(version)]) => {
+            let version = match version.split('.').count() {
+                2 => parse_version(version),
+                _ => None,
+            }
+            .ok_or_else(|| Error::new(format!("malformed version `{version}`")))?;
+
+            rustc_version()? >= version
+        }
+        ("between", [low, high, Term::Ident(key)]) => {
+            let range = bound(low)?..=bound(high)?;
+            range
+                .into_iter()
+                .any(|value| has_cfg(cfgs, key, Some(&value.to_string())))
+        }
+        ("at_least", [needed, preds @ ..]) => count(preds, cfgs)? >= bound(needed)? as usize,
+        ("exactly", [needed, preds @ ..]) => count(preds, cfgs)? == bound(needed)? as usize,
+        ("at_most", [needed, preds @ ..]) => count(preds, cfgs)? <= bound(needed)? as usize,
+        ("one_of", preds) => count(preds, cfgs)? == 1,
+        ("majority", preds) => count(preds, cfgs)? > preds.len() / 2,
+        _ => {
+            return Err(Error::new(format!(
+                "malformed or unknown predicate `{name}`"
+            )))
+        }
+    })
+}
+
+fn count(preds: &[Term], cfgs: &Cfgs) -> Result<usize, Error> {
+    let mut count = 0;
+    for pred in preds {
+        count += usize::from(eval_pred(pred, cfgs)?);
+    }
+    Ok(count)
+}
+
+fn strings<'a>(name: &str, terms: &'a [Term]) -> Result<impl Iterator<Item = &'a str> + 'a, Error> {
+    if terms.iter().all(|term| matches!(term, Term::Str(_))) {
+        Ok(terms.iter().filter_map(|term| match term {
+            Term::Str(value) => Some(value.as_str()),
+            _ => None,
+        }))
+    } else {
+        Err(Error::new(format!(
+            "`{name}` expects one or more string literals"
+        )))
+    }
+}
+
+fn bound(term: &Term) -> Result<u32, Error> {
+    match term {
+        Term::Int(value @ 0..=32) => Ok(*value),
+        _ => Err(Error::new("expected an integer literal between 0 and 32")),
+    }
+}
+
+fn ptr_width(term: &Term) -> Result<u32, Error> {
+    match term {
+        Term::Int(width @ (16 | 32 | 64 | 128)) => Ok(*width),
+        _ => Err(Error::new("expected a pointer width of 16, 32, 64, or 128")),
+    }
+}
+
+fn target_ptr_width(cfgs: &Cfgs) -> Result<u32, Error> {
+    let width = match cfgs {
+        Cfgs::Env => env::var("CARGO_CFG_TARGET_POINTER_WIDTH").ok(),
+        Cfgs::Printed(printed) => printed
+            .iter()
+            .find(|(key, _)| key == "target_pointer_width")
+            .and_then(|(_, width)| width.clone()),
+    };
+
+    width
+        .and_then(|width| width.parse().ok())
+        .ok_or_else(|| match cfgs {
+            Cfgs::Env => Error::new("`CARGO_CFG_TARGET_POINTER_WIDTH` is not set"),
+            Cfgs::Printed(_) => Error::new("the target does not specify a pointer width"),
+        })
+}
+
+fn eval_cfg(term: &Term, cfgs: &Cfgs) -> Result<bool, Error> {
+    Ok(match term {
+        Term::Ident(key) => has_cfg(cfgs, key, None),
+        Term::KeyValue(key, value) => has_cfg(cfgs, key, Some(value)),
+        Term::Call(name, args) => match (name.as_str(), args.as_slice()) {
+            ("all", operands) => {
+                let mut truthy = true;
+                for cfg in operands {
+                    truthy &= eval_cfg(cfg, cfgs)?;
+                }
+                truthy
+            }
+            ("any", operands) => {
+                let mut truthy = false;
+                for cfg in operands {
+                    truthy |= eval_cfg(cfg, cfgs)?;
+                }
+                truthy
+            }
+            ("not", [cfg]) => !eval_cfg(cfg, cfgs)?,
+            _ => return Err(Error::new(format!("malformed cfg predicate `{name}`"))),
+        },
+        _ => return Err(Error::new("expected a cfg predicate")),
+    })
+}
+
+fn has_cfg(cfgs: &Cfgs, key: &str, value: Option<&str>) -> bool {
+    let var = |prefix: &str, name: &str| {
+        env::var(format!("{prefix}{}", name.to_uppercase().replace('-', "_")))
+    };
+
+    match (cfgs, key, value) {
+        // Features belong to the crate being built rather than to its target.
+        (_, "feature", Some(name)) => var("CARGO_FEATURE_", name).is_ok(),
+        (Cfgs::Env, key, None) => var("CARGO_CFG_", key).is_ok(),
+        (Cfgs::Env, key, Some(value)) => var("CARGO_CFG_", key)
+            .is_ok_and(|values| values.split(',').any(|candidate| candidate == value)),
+        (Cfgs::Printed(printed), key, value) => {
+            printed.iter().any(|(candidate, candidate_value)| {
+                candidate == key && candidate_value.as_deref() == value
+            })
+        }
+    }
+}
@@ -96,9 +96,50 @@
 //!
 //! - `false()`: is always falsy
 //!
+//! - `true` and `false`: shorthands for `true()` and `false()`. These are currently only supported
+//!   in the branches of [`cond!`](crate::cond), either as the entire predicate of a branch or
+//!   within the block forms of `all` and `any` below.
+//!
 //! - `cfg(<cfg input>)`: resolves to the result of a regular [cfg attribute][cfg_attr] with the
 //!   same input.
 //!
+//! - `cfg_not(<cfg input>)`: resolves to the negation of the corresponding `cfg(...)` predicate.
+//!   `cfg_not(windows)` is equivalent to `not(cfg(windows))` and, like it, is folded into a single
+//!   `#[cfg(not(windows))]` attribute.
+//!
+//! - `cfg_eq(<key>, <value>)`: resolves to truthy if the cfg key is set to the provided string
+//!   literal. `cfg_eq(key, "value")` is equivalent to `cfg(key = "value")`.
+//!
+//! - `feature(<name 1>, <name 2>, ...)`: resolves to truthy if all the provided features, given as
+//!   string literals, are enabled. `feature("name")` is equivalent to `cfg(feature = "name")`.
+//!
+//! - `target_os(<name 1>, <name 2>, ...)`: resolves to truthy if the target operating system is any
+//!   of the provided string literals. `target_os("name")` is equivalent to
+//!   `cfg(target_os = "name")`.
+//!
+//! - `rustc_at_least("<major>.<minor>")`: resolves to truthy if the version of the compiler is at
+//!   least the provided version. See [Compiler Versions](#compiler-versions) for details.
+//!
+//! - `ptr_width_eq(<width>)`: resolves to truthy if the target's pointer width, in bits, is equal to
+//!   the provided integer literal.
+//!
+//! - `ptr_width_at_least(<width>)`: resolves to truthy if the target's pointer width, in bits, is
+//!   greater than or equal to the provided integer literal.
+//!
+//!   Both of these predicates only consider the pointer widths `16`, `32`, `64`, and `128`.
+//!   Providing any other width is a compile error. Because `rustc` does not know of any target
+//!   with a 128-bit pointer width, `128` is only checked when it is explicitly provided.
+//!
+//! - `between(<low>, <high>, <key>)`: resolves to truthy if the cfg `key` has a value between the
+//!   integer literals `low` and `high`, inclusive. This is useful for integer-valued custom cfgs set
+//!   by build scripts. Both bounds must be between `0` and `32` and every value in the range is
+//!   checked using `cfg(key = "<value>")`, so all of them should be declared in the build script's
+//!   `cargo::rustc-check-cfg` instruction. `between(low, high, key)` with `low` greater than
+//!   `high` always resolves to false.
+//!
+//! - `(<predicate>)`: resolves to the result of the provided `cfgenius` predicate. This can be used
+//!   to visually group sub-predicates.
+//!
 //! - `not(<predicate>)`: negates the resolution of the provided `cfgenius` predicate.
 //!
 //! - `all(<predicate 1>, <predicate 2>, ...)`: resolves to truthy if none of the provided `cfgenius`
@@ -107,11 +148,387 @@
 //! - `any(<predicate 1>, <predicate 2>, ...)`: resolves to truthy if at least of the provided `cfgenius`
 //!   predicates succeed. `any()` with no provided predicates resolves to false.
 //!
+//! - `all { <predicate 1>; <predicate 2>; ... }` and `any { <predicate 1>; <predicate 2>; ... }`:
+//!   block forms of `all` and `any` which can make large predicate trees easier to read. These
+//!   are currently only supported in the branches of [`cond!`](crate::cond).
+//!
+//! - `nor(<predicate 1>, <predicate 2>, ...)`: resolves to truthy if none of the provided `cfgenius`
+//!   predicates succeed. This is equivalent to `not(any(...))` so `nor()` with no provided
+//!   predicates resolves to true.
+//!
+//! - `none_of(<predicate 1>, <predicate 2>, ...)`: an alias of `nor` for those who find the name
+//!   more readable. `none_of()` with no provided predicates resolves to true.
+//!
+//! - `nand(<predicate 1>, <predicate 2>, ...)`: resolves to truthy if at least one of the provided
+//!   `cfgenius` predicates fails. This is equivalent to `not(all(...))` so `nand()` with no provided
+//!   predicates resolves to false.
+//!
+//! - `xor(<predicate 1>, <predicate 2>)`: resolves to truthy if exactly one of the two provided
+//!   `cfgenius` predicates succeeds. Providing any other number of predicates is a compile error.
+//!
+//! - `implies(<antecedent>, <consequent>)`: resolves to truthy if the antecedent predicate fails or
+//!   the consequent predicate succeeds. Providing any other number of predicates is a compile error.
+//!
+//! - `iff(<predicate 1>, <predicate 2>)`: resolves to truthy if both provided predicates succeed or
+//!   both of them fail. Providing any other number of predicates is a compile error.
+//!
+//! - `at_least(<count>, <predicate 1>, <predicate 2>, ...)`: resolves to truthy if at least `count`
+//!   of the provided `cfgenius` predicates succeed. `count` must be an integer literal between `0`
+//!   and `32`. `at_least(0, ...)` always resolves to true.
+//!
+//! - `exactly(<count>, <predicate 1>, <predicate 2>, ...)`: resolves to truthy if exactly `count` of
+//!   the provided `cfgenius` predicates succeed. `count` follows the same rules as in `at_least`.
+//!
+//! - `one_of(<predicate 1>, <predicate 2>, ...)`: resolves to truthy if exactly one of the provided
+//!   `cfgenius` predicates succeeds. This is equivalent to `exactly(1, ...)` and is useful for
+//!   asserting that exactly one of several mutually exclusive options is selected.
+//!
+//! - `at_most(<count>, <predicate 1>, <predicate 2>, ...)`: resolves to truthy if no more than
+//!   `count` of the provided `cfgenius` predicates succeed. `count` follows the same rules as in
+//!   `at_least`. `at_most(<count>)` with no provided predicates always resolves to true.
+//!
+//! - `majority(<predicate 1>, <predicate 2>, ...)`: resolves to truthy if strictly more than half
+//!   of the provided `cfgenius` predicates succeed. Hence, exactly half of the predicates
+//!   succeeding is not a majority and `majority()` with no provided predicates resolves to false.
+//!
 //! - `macro(<path to macro>)`: uses the macro to determine the truthiness of the predicate.
 //!
 //! - `macro(<path to macro> => <macro arguments>)`: uses the macro with the provided arguments to
 //!   determine the truthiness of the predicate.
 //!
+//! - `all_spliced(<path to macro>)` and `any_spliced(<path to macro>)`: resolve to the result of
+//!   `all(...)` and `any(...)`, respectively, applied to the list of predicates produced by the
+//!   macro. Like with `macro(...)`, arguments can be passed to the macro by writing
+//!   `all_spliced(<path to macro> => <macro arguments>)`. See the
+//!   [spliced predicate lists](#spliced-predicate-lists) section for how to write such a macro.
+//!
+//! - `macro(!<path to macro>)` and `macro(!<path to macro> => <macro arguments>)`: resolves to the
+//!   negation of the corresponding `macro(...)` predicate. This is equivalent to
+//!   `not(macro(...))`.
+//!
+//! Every predicate which takes a list of arguments accepts an optional trailing comma.
+//!
+//! Predicates are checked for well-formedness before being evaluated, even if they belong to a
+//! branch which is never taken. The contents of `cfg(...)` and the arguments passed to custom
+//! variables are left for `rustc` and the variable to validate, respectively.
+//!
+//! For example:
+//!
+//! ```
+//! const _: () = {
+//!     assert!(cfgenius::cond_expr!(all(true(), not(false()))));
+//!     assert!(cfgenius::cond_expr!(any(false(), true())));
+//!     assert!(cfgenius::cond_expr!(not(cfg(any()))));
+//!     assert!(cfgenius::cond_expr!(not(not(cfg(all())))));
+//!     assert!(cfgenius::cond_expr!(cfg_not(any())));
+//!     assert!(!cfgenius::cond_expr!(cfg_not(all())));
+//!     assert!(cfgenius::cond_expr!(all(true(), cfg_not(any()), not(cfg_not(all())))));
+//!     assert!(cfgenius::cond_expr!(any(cfg_not(all()), cfg_not(not(all())))));
+//!     assert!(!cfgenius::cond_expr!(not(not(not(true())))));
+//!     assert!(cfgenius::cond_expr!(all(true(), (any(false(), true())))));
+//!     assert!(cfgenius::cond_expr!(nor(false(), false())));
+//!     assert!(!cfgenius::cond_expr!(nor(false(), true())));
+//!     assert!(cfgenius::cond_expr!(nor()));
+//!     assert!(cfgenius::cond_expr!(none_of(false(), cfg(any()))));
+//!     assert!(!cfgenius::cond_expr!(none_of(false(), true())));
+//!     assert!(cfgenius::cond_expr!(none_of()));
+//!
+//!     assert!(cfgenius::cond_expr!(nand(false(), false())));
+//!     assert!(cfgenius::cond_expr!(nand(true(), false())));
+//!     assert!(!cfgenius::cond_expr!(nand(true(), true())));
+//!     assert!(cfgenius::cond_expr!(nand(true(), false(), true())));
+//!     assert!(!cfgenius::cond_expr!(nand(true(), true(), true())));
+//!     assert!(!cfgenius::cond_expr!(nand()));
+//!     assert!(!cfgenius::cond_expr!(feature("this_feature_does_not_exist")));
+//!     assert!(cfgenius::cond_expr!(xor(
+//!         cfg_eq(target_endian, "little"),
+//!         cfg_eq(target_endian, "big"),
+//!     )));
+//!     assert!(cfgenius::cond_expr!(implies(target_os("linux", "android"), cfg(unix))));
+//!     assert!(cfgenius::cond_expr!(ptr_width_at_least(16)));
+//!     assert!(
+//!         cfgenius::cond_expr!(between(0, 32, target_pointer_width))
+//!             == cfg!(any(target_pointer_width = "16", target_pointer_width = "32"))
+//!     );
+//!     assert!(
+//!         cfgenius::cond_expr!(between(17, 32, target_pointer_width))
+//!             == cfg!(target_pointer_width = "32")
+//!     );
+//!     assert!(!cfgenius::cond_expr!(between(8, 4, target_pointer_width)));
+//!     assert!(cfgenius::cond_expr!(exactly(
+//!         1,
+//!         ptr_width_eq(16),
+//!         ptr_width_eq(32),
+//!         ptr_width_eq(64),
+//!     )));
+//!
+//!     assert!(cfgenius::cond_expr!(xor(true(), false())));
+//!     assert!(!cfgenius::cond_expr!(xor(true(), true())));
+//!     assert!(!cfgenius::cond_expr!(xor(false(), false())));
+//!
+//!     assert!(cfgenius::cond_expr!(implies(false(), false())));
+//!     assert!(!cfgenius::cond_expr!(implies(true(), false())));
+//!
+//!     assert!(!cfgenius::cond_expr!(one_of(false(), cfg(any()), false())));
+//!     assert!(cfgenius::cond_expr!(one_of(false(), true(), false())));
+//!     assert!(!cfgenius::cond_expr!(one_of(true(), cfg(all()), false())));
+//!     assert!(!cfgenius::cond_expr!(one_of()));
+//!
+//!     assert!(cfgenius::cond_expr!(iff(true(), true())));
+//!     assert!(!cfgenius::cond_expr!(iff(true(), false())));
+//!     assert!(!cfgenius::cond_expr!(iff(false(), true())));
+//!     assert!(cfgenius::cond_expr!(iff(false(), false())));
+//!
+//!     assert!(cfgenius::cond_expr!(at_least(2, true(), false(), true())));
+//!     assert!(!cfgenius::cond_expr!(at_least(2, true(), false(), false())));
+//!     assert!(cfgenius::cond_expr!(at_least(0)));
+//!     assert!(!cfgenius::cond_expr!(at_least(4, true(), true(), true())));
+//!
+//!     assert!(cfgenius::cond_expr!(exactly(0, false(), false())));
+//!     assert!(cfgenius::cond_expr!(exactly(2, true(), false(), true())));
+//!     assert!(!cfgenius::cond_expr!(exactly(2, true(), true(), true())));
+//!     assert!(!cfgenius::cond_expr!(exactly(2, true(), false(), false())));
+//!
+//!     assert!(cfgenius::cond_expr!(at_most(1, false(), true(), false())));
+//!     assert!(!cfgenius::cond_expr!(at_most(1, true(), true(), false())));
+//!     assert!(cfgenius::cond_expr!(at_most(0)));
+//!
+//!     assert!(cfgenius::cond_expr!(majority(true(), false(), true())));
+//!     assert!(!cfgenius::cond_expr!(majority(true(), false(), true(), false())));
+//!     assert!(cfgenius::cond_expr!(majority(true(), true(), true(), false())));
+//!     assert!(!cfgenius::cond_expr!(majority()));
+//!
+//!     assert!(cfgenius::cond_expr!(all(cfg(all()),)));
+//!     assert!(cfgenius::cond_expr!(any(cfg(any()), cfg(all()),)));
+//!     assert!(cfgenius::cond_expr!(xor(true(), false(),)));
+//!     assert!(cfgenius::cond_expr!(at_most(1, true(), false(),)));
+//!
+//!     cfgenius::define!(two_or_more = at_least(2, cfg(all()), false(), true()));
+//!     assert!(cfgenius::cond_expr!(macro(two_or_more)));
+//!     assert!(!cfgenius::cond_expr!(macro(!two_or_more)));
+//!
+//!     cfgenius::define!(both($a, $b) = all($a(), $b()));
+//!     assert!(cfgenius::cond_expr!(macro(both => true, true)));
+//!     assert!(cfgenius::cond_expr!(not(macro(both => true, false))));
+//!     assert!(cfgenius::cond_expr!(macro(!both => false, true)));
+//!     assert!(!cfgenius::cond_expr!(not(macro(both => true, true))));
+//! #
+//! #     // Long lists of operands must not hit the recursion limit, even when most of them are
+//! #     // variables.
+//! #     cfgenius::define! {
+//! #         is_on = true();
+//! #         is_off = false();
+//! #     }
+//! #
+//! #     assert!(cfgenius::cond_expr!(all(
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         cfg(all()),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         cfg(all()),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         cfg(all()),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         cfg(all()),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         cfg(all()),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #         macro(is_on), macro(is_on), macro(is_on), macro(is_on),
+//! #     )));
+//! #     assert!(!cfgenius::cond_expr!(any(
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         cfg(any()),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         cfg(any()),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         cfg(any()),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         cfg(any()),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         cfg(any()),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #         macro(is_off), macro(is_off), macro(is_off), macro(is_off),
+//! #     )));
+//! };
+//! ```
+//!
+//! Operands which are not predicates are reported when the predicate is checked. Bare cfg names
+//! and `key = "value"` pairs, which are easy to write out of habit from `#[cfg(...)]`, produce an
+//! error suggesting the `cfg(...)` predicate they were likely meant to be:
+//!
+//! ```compile_fail
+//! cfgenius::cond! {
+//!     if any(cfg(windows), all(unix, target_os = "linux")) {
+//!         //                   ^ error: expected a predicate like `cfg(unix)`, found `unix`
+//!     }
+//! }
+//! ```
+//!
+//! Nested `all`, `any`, and `not` groups are folded into a single `cfg` attribute wherever their
+//! operands allow it, so the depth of a predicate has little bearing on how much of the recursion
+//! limit it uses. Only operands which cannot be expressed as a `cfg` predicate, such as
+//! `macro(...)`, cost a level of expansion each, which leaves room for around a hundred of them.
+//! Typical predicates therefore never require raising `#![recursion_limit]`:
+//!
+//! ```
+//! cfgenius::define! {
+//!     is_enabled = true();
+//!     is_disabled = false();
+//! }
+//!
+//! cfgenius::cond! {
+//!     if all(
+//!         macro(is_enabled), cfg(all()), not(macro(is_disabled)), macro(is_enabled),
+//!         any(
+//!             macro(is_disabled), cfg(any()), false(), macro(!is_enabled),
+//!             all(
+//!                 macro(is_enabled), true(), cfg(not(any())), macro(!is_disabled),
+//!                 any(
+//!                     macro(is_disabled), macro(is_disabled), cfg(any()), not(macro(is_enabled)),
+//!                     all(
+//!                         macro(is_enabled), cfg(all()), macro(!is_disabled),
+//!                         any(cfg(unix), cfg(not(unix))),
+//!                     ),
+//!                 ),
+//!                 macro(is_enabled),
+//!             ),
+//!             macro(is_disabled),
+//!         ),
+//!         macro(is_enabled),
+//!     ) {
+//!         const NESTED: bool = true;
+//!     } else {
+//!         const NESTED: bool = false;
+//!     }
+//! }
+//! #
+//! # assert!(NESTED);
+//! ```
+//!
+//! ## Compiler Versions
+//!
+//! The version of the compiler is not exposed to macros or to regular `cfg` attributes. Instead,
+//! the `rustc_at_least("<major>.<minor>")` predicate checks the custom
+//! `cfgenius_rustc_at_least = "<major>.<minor>"` cfg, which is set for every version up to and
+//! including the version of the compiler by calling [`build::emit_rustc_version`] from the build
+//! script of the crate using the predicate. This requires the `build` feature of the
+//! `cfgenius` build dependency.
+//!
+//! Versions are compared at the granularity of minor releases so patch versions, e.g.
+//! `"1.70.1"`, never match. Without the build script, the predicate is always falsy and, since the
+//! cfg is then never declared through `cargo::rustc-check-cfg`, each use of it raises an
+//! `unexpected_cfgs` warning. Crates which only evaluate the predicate on some builds can declare
+//! the cfg in their manifest instead:
+//!
+//! ```toml
+//! [lints.rust.unexpected_cfgs]
+//! level = "warn"
+//! check-cfg = ["cfg(cfgenius_rustc_at_least, values(any()))"]
+//! ```
+//!
+//! ```no_compile
+//! // In `build.rs`...
+//! fn main() {
+//!     cfgenius::build::emit_rustc_version().unwrap();
+//! }
+//!
+//! // In `lib.rs`...
+//! cfgenius::cond! {
+//!     if rustc_at_least("1.80") {
+//!         pub fn lazy() -> std::sync::LazyLock<u32> {
+//!             std::sync::LazyLock::new(|| 4)
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ```
+//! const _: () = {
+//!     // Without the build script, this never matches.
+//!     assert!(!cfgenius::cond_expr!(rustc_at_least("1.0")));
+//! };
+//! ```
+//!
+//! ## Environment Variables
+//!
+//! Predicates are resolved by the rules of `macro_rules!` macros, which can only match on the
+//! tokens they are given and cannot branch on the output of another macro such as `env!` or
+//! `option_env!`. Hence, there is no `env(...)` predicate.
+//! Instead, read the environment variable in a build script and forward it to the compiler as a
+//! custom cfg:
+//!
+//! ```no_compile
+//! // In `build.rs`...
+//! fn main() {
+//!     println!("cargo::rerun-if-env-changed=MYCRATE_BACKEND");
+//!     println!("cargo::rustc-check-cfg=cfg(mycrate_backend, values(any()))");
+//!
+//!     if let Ok(backend) = std::env::var("MYCRATE_BACKEND") {
+//!         println!("cargo::rustc-cfg=mycrate_backend={backend:?}");
+//!     }
+//! }
+//!
+//! // In `lib.rs`...
+//! cfgenius::cond! {
+//!     if cfg_eq(mycrate_backend, "simd") {
+//!         // (SIMD backend)
+//!     } else {
+//!         // (scalar backend)
+//!     }
+//! }
+//! ```
+//!
+//! ## Constant Expressions
+//!
+//! For the same reason, predicates cannot depend on the value of a `const` expression and there is
+//! no `const(...)` predicate. A variable has to pick which tokens to emit while it is being
+//! expanded, long before `const` items are evaluated, and emitting both sets of tokens gated on the
+//! constant is not possible for items. When the condition is only needed in expression position,
+//! use a regular `if` on the constant, which the compiler folds away:
+//!
+//! ```
+//! const IS_BIG: bool = std::mem::size_of::<usize>() > 4;
+//!
+//! fn chunk_size() -> usize {
+//!     if IS_BIG { 4096 } else { 512 }
+//! }
+//! #
+//! # assert_eq!(chunk_size() == 4096, std::mem::size_of::<usize>() > 4);
+//! ```
+//!
 //! ## Custom Variables
 //!
 //! Most variables can be succinctly defined using [`define!`](crate::define). However, because
@@ -141,14 +558,244 @@
 //! more. If the variable should be falsy, the macro should expand to `/* falsy tokens */` and
 //! nothing more.
 //!
-//! These macros should be effectless and pure with respect to their environment. You should not
-//! rely on this macro being evaluated once for every time it appears in a predicate, even though
-//! this is the current behavior.
+//! These macros should be effectless and pure with respect to their environment. Every appearance
+//! of a `macro(...)` predicate is expanded at most once, so variables which emit large amounts of
+//! tokens do not slow compilation down further when nested inside other predicates. It may not be
+//! expanded at all if the result of the enclosing predicate is already decided by its other
+//! operands. With the `proc-macro` feature, [`cond!`](crate::cond) additionally expands a
+//! `macro(...)` predicate which appears several times within the predicate of a single branch only
+//! once, as long as at least one of its appearances is evaluated regardless of the other operands.
+//! This is an optimization of the procedural macro only: the `macro_rules!` implementation expands
+//! every appearance which is evaluated. Since features are unified across the dependency graph,
+//! another crate may decide which of the two is used, so variables must not rely on either.
+//!
+//! ```
+//! // A variable which would cause a duplicate definition error if it were expanded twice.
+//! macro_rules! counted {
+//!     (yes { $($yes:tt)* } no { $($no:tt)* }) => {
+//!         pub struct Expanded;
+//!         $($yes)*
+//!     };
+//! }
+//!
+//! mod xor {
+//!     cfgenius::cond! { if xor(macro(counted), true()) {} }
+//! }
+//!
+//! mod iff {
+//!     cfgenius::cond! { if iff(macro(counted), false()) {} }
+//! }
+//! #
+//! # mod any_all {
+//! #     cfgenius::cond! { if any(all(macro(counted), false()), not(true())) {} }
+//! # }
+//! #
+//! # mod exactly {
+//! #     cfgenius::cond! { if exactly(1, macro(counted), false()) {} }
+//! # }
+//! #
+//! # mod at_most {
+//! #     cfgenius::cond! { if at_most(1, true(), macro(counted)) {} }
+//! # }
+//! #
+//! # mod majority {
+//! #     cfgenius::cond! { if majority(false(), macro(counted), true()) {} }
+//! # }
+//! #
+//! # mod chain {
+//! #     cfgenius::cond! {
+//! #         if false() {
+//! #         } else if implies(macro(counted), false()) | true() {
+//! #         }
+//! #     }
+//! # }
+//!
+//! # fn main() {
+//! let _ = (xor::Expanded, iff::Expanded);
+//! # let _ = (any_all::Expanded, exactly::Expanded, at_most::Expanded);
+//! # let _ = (majority::Expanded, chain::Expanded);
+//! # }
+//! ```
+//!
+//! The path to the macro can take any form of a simple path, i.e. one without generic arguments.
+//! This includes paths starting with `self`, `super`, `crate`, `$crate`, or a leading `::`. The
+//! latter lets code generators refer to variables of other crates without being affected by names
+//! in scope at the invocation site:
+//!
+//! ```
+//! mod outer {
+//!     cfgenius::define!(pub is_outer = true());
+//!
+//!     pub mod inner {
+//!         cfgenius::define!(pub is_inner = true());
+//!
+//!         pub const FORMS: [bool; 5] = [
+//!             cfgenius::cond_expr!(macro(self::is_inner)),
+//!             cfgenius::cond_expr!(macro(super::is_outer)),
+//!             cfgenius::cond_expr!(macro(crate::outer::is_outer)),
+//!             cfgenius::cond_expr!(macro(::cfgenius::truthy)),
+//!             crate::dollar_crate!(),
+//!         ];
+//!     }
+//! }
+//!
+//! #[macro_export]
+//! macro_rules! dollar_crate {
+//!     () => { cfgenius::cond_expr!(macro($crate::outer::is_outer)) };
+//! }
+//! #
+//! cfgenius::define!(absolute = all(macro(::cfgenius::truthy), macro(!::cfgenius::falsy)));
+//! #
+//! # fn main() {
+//! #     assert_eq!(outer::inner::FORMS, [true; 5]);
+//! #     assert!(cfgenius::cond_expr!(macro(absolute)));
+//! #     assert!(cfgenius::cond_tokens!(if macro(::cfgenius::truthy) { true } else { false }));
+//! # }
+//! ```
+//!
+//! ## Spliced Predicate Lists
+//!
+//! The operands of `all(...)` and `any(...)` must be written out since the macro arguments of a
+//! predicate are never expanded. To produce such a list from another macro, use the
+//! `all_spliced(<path to macro>)` and `any_spliced(<path to macro>)` predicates. These evaluate
+//! `all_spliced(<path to macro> => <macro arguments>)` by expanding:
+//!
+//! ```no_compile
+//! path::to::macro! {
+//!     args { /* macro arguments, omitted if none were provided */ }
+//!     callback { /* path to a macro */ }
+//!     splice { /* opaque tokens */ }
+//! }
+//! ```
+//!
+//! The macro should then expand to an invocation of the callback macro containing the opaque
+//! tokens followed by the comma-separated list of predicates:
+//!
+//! ```no_compile
+//! /* path to a macro */! {
+//!     /* opaque tokens */
+//!     <predicate 1>, <predicate 2>, ...
+//! }
+//! ```
+//!
+//! Unlike other predicates, the predicates of the list are only checked for well-formedness once
+//! they are evaluated.
+//!
+//! ```
+//! macro_rules! desktop_targets {
+//!     (callback { $($callback:tt)* } splice { $($splice:tt)* }) => {
+//!         $($callback)*! {
+//!             $($splice)*
+//!             target_os("linux"), target_os("macos"), target_os("windows")
+//!         }
+//!     };
+//! }
+//!
+//! macro_rules! with_extra_target {
+//!     (args { $os:literal } callback { $($callback:tt)* } splice { $($splice:tt)* }) => {
+//!         $($callback)*! { $($splice)* target_os($os), any_spliced(desktop_targets) }
+//!     };
+//! }
+//!
+//! cfgenius::cond! {
+//!     if any_spliced(with_extra_target => "freebsd") {
+//!         fn is_supported() -> bool { true }
+//!     } else {
+//!         fn is_supported() -> bool { false }
+//!     }
+//! }
+//! #
+//! # assert_eq!(
+//! #     is_supported(),
+//! #     cfg!(any(
+//! #         target_os = "linux",
+//! #         target_os = "macos",
+//! #         target_os = "windows",
+//! #         target_os = "freebsd",
+//! #     )),
+//! # );
+//! # assert!(!cfgenius::cond_expr!(all_spliced(desktop_targets)));
+//! #
+//! # macro_rules! keyword_args {
+//! #     (args { true { all } } callback { $($callback:tt)* } splice { $($splice:tt)* }) => {
+//! #         $($callback)*! { $($splice)* true() }
+//! #     };
+//! # }
+//! #
+//! # cfgenius::cond! {
+//! #     if all { true, all_spliced(keyword_args => true { all }) } {
+//! #         const KEPT: bool = true;
+//! #     } else {
+//! #         const KEPT: bool = false;
+//! #     }
+//! # }
+//! # assert!(KEPT);
+//! ```
+//!
+//! ## Crate Features
+//!
+//! - `attr`: enables the [`#[when(...)]`](crate::when) attribute, which is the attribute
+//!   counterpart of [`cond!`](crate::cond). This pulls in a procedural macro dependency.
+//! - `proc-macro`: replaces the entry point of [`cond!`](crate::cond) with a procedural macro. The
+//!   procedural macro only handles chains in which every predicate can be expressed as a regular
+//!   cfg predicate, lowering them directly into one `#[cfg(...)]`-gated item per branch, which
+//!   compiles faster and never runs into the recursion limit. Chains which use `macro(...)`,
+//!   `all_spliced`, or `any_spliced`, as well as malformed chains, are silently forwarded to the
+//!   regular `macro_rules!` implementation. Their accepted syntax and reported errors are therefore
+//!   unchanged, but so is the share of the recursion limit they use, except that variables which
+//!   appear several times in the predicate of a branch are only expanded once, as described in
+//!   [Custom Variables](#custom-variables).
+//! - `build`: enables the [`build`] module, which evaluates predicates from a build script and
+//!   exposes their results as custom cfgs. It can also check predicates against a list of target
+//!   triples, e.g. in CI. Only predicates which resolve to cfgs are supported.
+//!
+//! ## `no_std` Support
+//!
+//! This crate is `#![no_std]` and everything its macros expand to only refers to `core`, so they
+//! can be used from `no_std` crates regardless of which features are enabled. The `build` feature
+//! is the only one which depends on `std`, which is fine since build scripts always run on the
+//! host. The `no-std-test` crate in the repository checks this by using the macros from a
+//! `#![no_std]` crate.
 //!
 //! [cfg_if]: https://docs.rs/cfg-if/1.0.0/cfg_if/index.html
 //! [cfg_attr]: https://doc.rust-lang.org/reference/conditional-compilation.html
 
-// #![no_std]
+#![no_std]
+
+// Build scripts always run on the host so the `build` module is free to use `std`.
+#[cfg(feature = "build")]
+extern crate std;
+
+#[cfg(feature = "build")]
+pub mod build;
+
+#[doc(hidden)]
+pub mod __private {
+    /// Selects `T` if the `COND` of the implementing [`Cond`] is `true` and `F` otherwise.
+    pub trait Select<T: ?Sized, F: ?Sized> {
+        type Output: ?Sized;
+    }
+
+    pub struct Cond<const COND: bool>;
+
+    impl<T: ?Sized, F: ?Sized> Select<T, F> for Cond<true> {
+        type Output = T;
+    }
+
+    impl<T: ?Sized, F: ?Sized> Select<T, F> for Cond<false> {
+        type Output = F;
+    }
+
+    /// Ensures that the branches of `cond_str!` produce a string slice.
+    pub const fn as_str(value: &'static str) -> &'static str {
+        value
+    }
+
+    /// Ensures that the branches of `cond_expr!` produce a value of the declared type.
+    pub const fn ascribe<T>(value: T) -> T {
+        value
+    }
+}
 
 /// A conditionally-compiled statement or item.
 ///
@@ -166,326 +813,6604 @@
 /// }
 /// ```
 ///
-/// See the [predicates](index.html#predicates) section of the crate documentation for more
-/// information about the predicate grammar.
+/// If none of the predicates match and there is no `else` branch, nothing is emitted. Use
+/// [`cond_exhaustive!`](crate::cond_exhaustive) to make this case a compile error instead.
+///
+/// The `else` keyword of the final branch can also be omitted, leaving a trailing `{ ... }` block
+/// which reads like a fallthrough. Like an `else` branch, it is only emitted if none of the
+/// branches before it match:
+///
+/// ```
+/// cfgenius::cond! {
+///     if cfg(any()) {
+///         const BACKEND: &str = "unreachable";
+///     } else if true() {
+///         const BACKEND: &str = "first";
+///     } else if cfg(all()) {
+///         const BACKEND: &str = "second";
+///     } {
+///         const BACKEND: &str = "fallback";
+///     }
+/// }
+///
+/// cfgenius::cond! {
+///     if false() | cfg(any()) {
+///         const LEVEL: u8 = 1;
+///     } {
+///         const LEVEL: u8 = 0;
+///     }
+/// }
+/// #
+/// # assert_eq!((BACKEND, LEVEL), ("first", 0));
+/// ```
+///
+/// This shorthand is shared by [`cond_stmt!`](crate::cond_stmt),
+/// [`cond_let!`](crate::cond_let) and [`cond_exhaustive!`](crate::cond_exhaustive), which accept
+/// the same chains as `cond!`. The other macros taking a chain of branches, such as
+/// [`cond_expr!`](crate::cond_expr), keep to their documented syntax and require the `else`
+/// keyword.
+///
+/// Several predicates can share a single branch by separating them with `|`. The branch is taken
+/// if any of them is truthy, just as if they had been wrapped in `any(...)`:
+///
+/// ```
+/// cfgenius::define!(is_wasi = cfg(target_os = "wasi"));
+///
+/// cfgenius::cond! {
+///     if cfg(unix) | cfg(windows) | macro(is_wasi) {
+///         fn has_filesystem() -> bool { true }
+///     } else {
+///         fn has_filesystem() -> bool { false }
+///     }
+/// }
+/// #
+/// # assert_eq!(has_filesystem(), cfg!(any(unix, windows, target_os = "wasi")));
+/// ```
+///
+/// Branch bodies are never parsed by the macro, so they can contain any item or statement,
+/// including `unsafe` functions, traits, and implementations:
+///
+/// ```
+/// cfgenius::define!(has_fast_load = any(cfg(target_arch = "x86_64"), cfg(target_arch = "aarch64")));
+///
+/// cfgenius::cond! {
+///     if macro(has_fast_load) {
+///         /// # Safety
+///         ///
+///         /// `ptr` must be valid for reads.
+///         unsafe fn load(ptr: *const u32) -> u32 {
+///             unsafe { ptr.read_volatile() }
+///         }
+///     } else {
+///         /// # Safety
+///         ///
+///         /// `ptr` must be valid for reads.
+///         unsafe fn load(ptr: *const u32) -> u32 {
+///             unsafe { ptr.read() }
+///         }
+///     }
+/// }
+///
+/// let value = 5;
+/// assert_eq!(unsafe { load(&value) }, 5);
+/// ```
+///
+/// The contents of the matching branch are emitted with their original spans so errors within them
+/// are reported just as they would be without the macro:
+///
+/// ```compile_fail,E0308
+/// cfgenius::cond! {
+///     if all(true(), not(false())) {
+///         const VALUE: u32 = "not a number";
+///     }
+/// }
+/// ```
+///
+/// Chains which do not follow the `if`, `else if`, `else` structure above are rejected with an
+/// error pointing out where the structure breaks down, such as a branch following the final `else`
+/// branch:
+///
+/// ```compile_fail
+/// cfgenius::cond! {
+///     if cfg(unix) {
+///         fn backend() {}
+///     } else {
+///         fn backend() {}
+///     } else if cfg(windows) {
+///         // error: found another branch after the final `else` branch; ...
+///         fn backend() {}
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// cfgenius::cond! {
+///     if cfg(unix) {
+///         fn backend() {}
+///     } if cfg(windows) {
+///         // error: expected `else` between two branches; ...
+///         fn backend() {}
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// cfgenius::cond! {
+///     else {
+///         // error: expected the chain to begin with `if <predicate> { ... }`, found `else`; ...
+///         fn backend() {}
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// cfgenius::cond! {
+///     if cfg(unix) | {
+///         // error: expected a predicate after `if`, found `{ ... }`
+///         fn backend() {}
+///     }
+/// }
+/// ```
+///
+/// Outer attributes at the start of a branch, including doc comments, therefore remain attached to
+/// the item or statement following them:
+///
+/// ```
+/// #![deny(dead_code)]
+///
+/// cfgenius::cond! {
+///     if all(cfg(all()), true()) {
+///         #[inline]
+///         #[allow(dead_code)]
+///         fn unused() {}
+///
+///         /// A value which can be copied.
+///         #[derive(Debug, Default, Clone, Copy)]
+///         #[cfg_attr(all(), derive(PartialEq))]
+///         struct Value(u32);
+///     }
+/// }
+///
+/// fn main() {
+///     cfgenius::cond! {
+///         if true() {
+///             #[allow(unused_variables)]
+///             let unused = Value::default();
+///         }
+///     }
+///
+///     assert_eq!(Value(1), Value(1).clone());
+/// }
+/// ```
+///
+/// Inner attributes of the items in a branch, e.g. those at the top of a module body, are forwarded
+/// verbatim too. The branch itself, however, cannot start with an inner attribute: its contents are
+/// spliced into the surrounding module or block rather than forming a new one, so there is nothing
+/// for the attribute to apply to and the compiler rejects it. Gate a whole module by placing the
+/// module declaration in the branch and the inner attributes inside it instead:
+///
+/// ```
+/// #![deny(dead_code)]
+///
+/// cfgenius::cond! {
+///     if any(cfg(test), true()) {
+///         mod tests {
+///             #![allow(dead_code)]
+///
+///             fn helper() {}
+///         }
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// cfgenius::cond! {
+///     if true() {
+///         // error: an inner attribute is not permitted in this context
+///         #![allow(dead_code)]
+///
+///         fn helper() {}
+///     }
+/// }
+/// ```
+///
+/// The macro can also be used wherever associated items are expected, i.e. inside `impl` and
+/// `trait` bodies:
+///
+/// ```
+/// cfgenius::define!(is_64_bit = cfg(target_pointer_width = "64"));
+///
+/// trait Word {
+///     type Repr;
+///
+///     const BITS: u32;
+/// }
+///
+/// struct Native;
+///
+/// impl Word for Native {
+///     cfgenius::cond! {
+///         if macro(is_64_bit) {
+///             type Repr = u64;
+///
+///             const BITS: u32 = 64;
+///         } else {
+///             type Repr = u32;
+///
+///             const BITS: u32 = 32;
+///         }
+///     }
+/// }
+///
+/// impl Native {
+///     cfgenius::cond! {
+///         if macro(is_64_bit) {
+///             fn describe() -> &'static str { "wide" }
+///         } else {
+///             fn describe() -> &'static str { "narrow" }
+///         }
+///     }
+/// }
+/// #
+/// # assert_eq!(Native::BITS == 64, cfg!(target_pointer_width = "64"));
+/// # assert_eq!(std::mem::size_of::<<Native as Word>::Repr>() * 8, Native::BITS as usize);
+/// # assert_eq!(Native::describe() == "wide", cfg!(target_pointer_width = "64"));
+/// ```
+///
+/// Likewise, foreign functions and statics can be declared conditionally inside `extern` blocks:
+///
+/// ```
+/// cfgenius::define!(is_posix = any(cfg(unix), target_os("wasi")));
+///
+/// extern "C" {
+///     cfgenius::cond! {
+///         if macro(is_posix) {
+///             fn getpid() -> i32;
+///
+///             #[allow(dead_code)]
+///             static environ: *const *const u8;
+///         } else if cfg(windows) {
+///             #[link_name = "_getpid"]
+///             fn getpid() -> i32;
+///         }
+///     }
+///
+///     cfgenius::cond! {
+///         if any(macro(is_posix), cfg(windows)) {
+///             fn abs(value: i32) -> i32;
+///         }
+///     }
+/// }
+///
+/// cfgenius::cond! {
+///     if any(macro(is_posix), cfg(windows)) {
+///         fn current_pid() -> i32 {
+///             unsafe { getpid() }
+///         }
+///     } else {
+///         fn current_pid() -> i32 {
+///             0
+///         }
+///     }
+/// }
+/// #
+/// # let _ = current_pid();
+/// # cfgenius::cond! {
+/// #     if any(macro(is_posix), cfg(windows)) {
+/// #         assert_eq!(unsafe { abs(-3) }, 3);
+/// #     }
+/// # }
+/// ```
+///
+/// Branch bodies are forwarded token for token, so `use` declarations with arbitrary trees,
+/// including nested groups, `self` imports, renames, and globs, can be gated like any other item:
+///
+/// ```
+/// mod backend {
+///     pub mod fast {
+///         pub fn run() -> &'static str { "fast" }
+///
+///         pub mod util {
+///             pub fn name() -> &'static str { "util" }
+///         }
+///     }
+///
+///     pub mod slow {
+///         pub fn run() -> &'static str { "slow" }
+///     }
+/// }
+///
+/// cfgenius::cond! {
+///     if all(cfg(all()), not(false())) {
+///         use crate::backend::{fast::{self, run as run_backend, util::{self as helpers, *}}};
+///         use std::collections::{hash_map::{self, Entry}, HashMap as Map};
+///     } else {
+///         use crate::backend::slow::{self as fast, run as run_backend};
+///     }
+/// }
+/// #
+/// # fn main() {
+/// #     assert_eq!(run_backend(), "fast");
+/// #     assert_eq!(fast::run(), "fast");
+/// #     assert_eq!(helpers::name(), name());
+/// #     let mut map = Map::<u8, u8>::new();
+/// #     assert!(matches!(map.entry(1), Entry::Vacant(_)));
+/// #     let _: hash_map::HashMap<u8, u8> = map;
+/// # }
+/// ```
+///
+/// This also means that `macro_rules!` definitions, along with their `$` metavariables and
+/// repetitions, are emitted unmodified. Since `cond!` does not introduce a scope of its own, the
+/// macros can be invoked by the code following it just as if they were defined at that location:
+///
+/// ```
+/// cfgenius::define!(is_64_bit = cfg(target_pointer_width = "64"));
+///
+/// cfgenius::cond! {
+///     if macro(is_64_bit) {
+///         macro_rules! word {
+///             ($name:ident) => { type $name = u64; };
+///         }
+///
+///         macro_rules! sum {
+///             ($($value:expr),* $(,)?) => { 0 $(+ $value)* };
+///         }
+///     } else {
+///         macro_rules! word {
+///             ($name:ident) => { type $name = u32; };
+///         }
+///
+///         macro_rules! sum {
+///             ($($value:expr),* $(,)?) => { 0 $(+ $value)* };
+///         }
+///     }
+/// }
+///
+/// word!(Word);
+///
+/// fn total() -> u32 {
+///     cfgenius::cond! {
+///         if true() {
+///             macro_rules! double {
+///                 ($value:expr) => { $value * 2 };
+///             }
+///         }
+///     }
+///
+///     double!(sum!(1, 2, 3))
+/// }
+/// #
+/// # assert_eq!(std::mem::size_of::<Word>() * 8, if cfg!(target_pointer_width = "64") { 64 } else { 32 });
+/// # assert_eq!(total(), 12);
+/// ```
+///
+/// Invocations of other macros which expand to items can be gated the same way. A `#[cfg(...)]`
+/// attribute is evaluated before the macro invocation it is applied to is expanded, so the
+/// invocations in branches which do not match are removed without ever being expanded and may even
+/// refer to macros which do not exist on the target being compiled for. The invocations in the
+/// matching branch expand exactly like they would outside of `cond!`, including any macros they
+/// define themselves:
+///
+/// ```
+/// cfgenius::define!(is_fast = any(cfg(all()), false()));
+///
+/// macro_rules! my_items {
+///     ($name:ident = $value:expr $(, $getter:ident)?) => {
+///         pub const $name: u32 = $value;
+///
+///         $(macro_rules! $getter {
+///             () => { $name };
+///         })?
+///     };
+/// }
+///
+/// cfgenius::cond! {
+///     if cfg(target_pointer_width = "64") {
+///         my_items!(WIDTH = 64, width);
+///     } else if cfg(any()) {
+///         this_macro_does_not_exist!();
+///     } else {
+///         my_items!(WIDTH = 32, width);
+///     }
+/// }
+///
+/// cfgenius::cond! {
+///     if macro(is_fast) {
+///         my_items! { DOUBLE_WIDTH = width!() * 2 }
+///     } else {
+///         neither_does_this_one!();
+///     }
+/// }
+/// #
+/// # assert_eq!(WIDTH == 64, cfg!(target_pointer_width = "64"));
+/// # assert_eq!(DOUBLE_WIDTH, WIDTH * 2);
+/// ```
+///
+/// Branches whose bodies consist solely of attributes, such as doc comments, expand to nothing
+/// since there is no item or statement for the attributes to apply to. This makes it possible to
+/// leave notes in branches which don't emit anything, e.g. while documenting a configuration:
+///
+/// ```
+/// cfgenius::cond! {
+///     if true() {
+///         /// This configuration needs no extra items.
+///     } else {
+///         compile_error!("unreachable");
+///     }
+/// }
+///
+/// fn len(values: &[u32]) -> usize {
+///     cfgenius::cond! {
+///         if cfg(all()) {
+///             /// Always taken.
+///             #[allow(unused)]
+///         }
+///     }
+///
+///     values.len()
+/// }
+/// #
+/// # assert_eq!(len(&[1, 2]), 2);
+/// ```
+///
+/// Large predicates can also be written using the block forms of `all` and `any`, whose
+/// predicates are separated by semicolons:
+///
+/// ```
+/// cfgenius::cond! {
+///     if all {
+///         cfg(target_has_atomic = "64");
+///         any {
+///             cfg(target_arch = "x86_64");
+///             cfg(target_arch = "aarch64");
+///         };
+///         not(cfg(miri));
+///     } {
+///         type Counter = std::sync::atomic::AtomicU64;
+///     } else {
+///         type Counter = std::sync::Mutex<u64>;
+///     }
+/// }
+/// #
+/// # let _ = Counter::new(0);
+/// ```
+///
+/// The keywords `true` and `false` can be used in place of the `true()` and `false()` predicates,
+/// either as the entire predicate of a branch or within the block forms. This only applies to
+/// predicates, so `true` and `false` keep their usual meaning in the branches themselves:
+///
+/// ```
+/// cfgenius::cond! {
+///     if false {
+///         compile_error!("this branch is never taken");
+///     } else if any { false; all { true; cfg(all()) } } {
+///         const ENABLED: bool = if true { true } else { false };
+///     } else {
+///         const ENABLED: bool = false;
+///     }
+/// }
+/// #
+/// # assert!(ENABLED);
+/// ```
+///
+/// Within the parenthesized forms of predicates, the keywords must still be written as `true()`
+/// and `false()`:
+///
+/// ```compile_fail
+/// cfgenius::cond! {
+///     if all(cfg(unix), true) {
+///         const ENABLED: bool = true;
+///     }
+/// }
+/// ```
+///
+/// With the `proc-macro` feature, a variable which appears several times in the predicate of a
+/// branch is only expanded once. Without it, every appearance which is evaluated is expanded, so
+/// unlike this example, variables shouldn't depend on how often they are expanded:
+///
+#[cfg_attr(feature = "proc-macro", doc = "```")]
+#[cfg_attr(not(feature = "proc-macro"), doc = "```ignore")]
+/// // A variable which would cause a duplicate definition error if it were expanded twice.
+/// macro_rules! counted {
+///     (yes { $($yes:tt)* } no { $($no:tt)* }) => {
+///         pub struct Expanded;
+///         $($yes)*
+///     };
+/// }
+///
+/// mod repeated {
+///     cfgenius::cond! {
+///         if all(cfg(all()), macro(counted), any(not(macro(!counted)), cfg(unix))) {
+///             pub const ENABLED: bool = true;
+///         } else {
+///             pub const ENABLED: bool = false;
+///         }
+///     }
+/// }
+///
+/// assert!(repeated::ENABLED);
+/// let _ = repeated::Expanded;
+/// ```
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + $($(else)? {
+            $($no:tt)*
+        })?
+    ) => {};
+}
+
+#[cfg(all(not(doc), not(feature = "proc-macro")))]
+#[macro_export]
+macro_rules! cond {
+    ($($tokens:tt)*) => {
+        $crate::__cond! { $($tokens)* }
+    };
+}
+
+// The procedural macro can't refer to this crate on its own, so it is passed `$crate`.
+#[cfg(all(not(doc), feature = "proc-macro"))]
+#[macro_export]
+macro_rules! cond {
+    ($($tokens:tt)*) => {
+        $crate::__cond_proc! { [$crate] $($tokens)* }
+    };
+}
+
+#[cfg(all(not(doc), feature = "proc-macro"))]
+#[doc(hidden)]
+pub use cfgenius_macros::cond as __cond_proc;
+
+#[cfg(not(doc))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cond {
+    // Branch bodies are only ever forwarded as `tt`s, never as opaque fragments or through
+    // `stringify!`, so that the tokens of the branch which is eventually emitted keep their
+    // original spans and errors within them point at the user's code.
+
+    // We begin by implementing `cond!` for one level of `if ... { ... } else { ... }`.
+
+    // true
+    (
+        @__internal_single_munch
+        if true() {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($yes)*
+    };
+
+    // false
+    (
+        @__internal_single_munch
+        if false() {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($no)*
+    };
+
+    // group
+    (
+        @__internal_single_munch
+        if ($($pred:ident)? ($($pred_args:tt)*)) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($pred)? ($($pred_args)*) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if ($($group:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(::core::concat!(
+            "expected exactly one predicate inside parentheses, found `(",
+            ::core::stringify!($($group)*),
+            ")`",
+        ));
+    };
+
+    // cfg
+    //
+    // Bodies which consist solely of attributes, e.g. a branch which only holds doc comments, have
+    // nothing for the attributes to apply to so they are dropped rather than emitted as-is.
+    (@__internal_id $(#[$($attr:tt)*])*) => {};
+    (@__internal_id $($id:tt)*) => { $($id)* };
+    (
+        @__internal_single_munch
+        if cfg($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        #[cfg($($args)*)] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($no)* }
+    };
+
+    // cfg_not
+    (
+        @__internal_single_munch
+        if cfg_not($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg($($args)*)] $crate::__cond! { @__internal_id $($no)* }
+    };
+
+    // cfg_eq
+    (
+        @__internal_single_munch
+        if cfg_eq($key:ident, $value:literal $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg($key = $value) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if cfg_eq($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`cfg_eq` expects a cfg key followed by a string literal value");
+    };
+
+    // feature
+    (
+        @__internal_single_munch
+        if feature($($name:literal),+ $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg(all($(feature = $name),+)) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if feature($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`feature` expects one or more feature names as string literals");
+    };
+
+    // target_os
+    (
+        @__internal_single_munch
+        if target_os($($name:literal),+ $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg(any($(target_os = $name),+)) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if target_os($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`target_os` expects one or more OS names as string literals");
+    };
+
+    // rustc_at_least
+    (
+        @__internal_single_munch
+        if rustc_at_least($version:literal $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg(cfgenius_rustc_at_least = $version) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if rustc_at_least($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(
+            "`rustc_at_least` expects a version of the form `\"<major>.<minor>\"` as a string literal"
+        );
+    };
+
+    // ptr_width_eq and ptr_width_at_least
+    (
+        @__internal_single_munch
+        if ptr_width_eq($width:tt $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_ptr_widths $width
+            @__internal_ptr_width_eq {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if ptr_width_at_least($width:tt $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_ptr_widths $width
+            @__internal_ptr_width_at_least {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+
+    // This table maps a pointer width to the list of known pointer widths greater than or equal to
+    // it before forwarding that list to the `@<continuation>` muncher. `"128"` is deliberately left
+    // out of the lower widths' lists since `rustc` would otherwise emit `unexpected_cfgs` warnings.
+    (@__internal_ptr_widths 16 @$cont:ident $($rest:tt)*) => {
+        $crate::__cond! { @$cont ["16" "32" "64"] $($rest)* }
+    };
+    (@__internal_ptr_widths 32 @$cont:ident $($rest:tt)*) => {
+        $crate::__cond! { @$cont ["32" "64"] $($rest)* }
+    };
+    (@__internal_ptr_widths 64 @$cont:ident $($rest:tt)*) => {
+        $crate::__cond! { @$cont ["64"] $($rest)* }
+    };
+    (@__internal_ptr_widths 128 @$cont:ident $($rest:tt)*) => {
+        $crate::__cond! { @$cont ["128"] $($rest)* }
+    };
+    (@__internal_ptr_widths $width:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a pointer width of 16, 32, 64, or 128, found `",
+            ::core::stringify!($width),
+            "`",
+        ));
+    };
+
+    (
+        @__internal_ptr_width_eq [$width:literal $($wider:literal)*] {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg(target_pointer_width = $width) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_ptr_width_at_least [$($width:literal)*] {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg(any($(target_pointer_width = $width),*)) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+
+    // not
+    //
+    // Negated `cfg` predicates are folded into the attribute itself and double negations cancel
+    // out so that the expansion never contains `not(not(...))`.
+    (
+        @__internal_single_munch
+        if not(cfg($($args:tt)*)) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg($($args)*)] $crate::__cond! { @__internal_id $($no)* }
+    };
+    (
+        @__internal_single_munch
+        if not(not($($pred:ident)? ($($pred_args:tt)*))) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($pred)?($($pred_args)*) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if not($($pred:ident)? ($($pred_args:tt)*)) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($pred)?($($pred_args)*) {
+                $($no)*
+            } else {
+                $($yes)*
+            }
+        }
+    };
+
+    // all and any
+    //
+    // Munching `all` and `any` operand by operand would nest every operand inside the expansion of
+    // the one before it, and every operand of a nested group inside the expansion of the group,
+    // quickly running into the recursion limit. Instead, the whole tree is folded into a single
+    // `cfg` predicate by an accumulator which keeps the unfinished groups on an explicit stack:
+    //
+    // ```plain_text
+    // @__internal_fold [<stack>] <group name> [<folded operands>] { <yes> } { <no> } <operands>
+    // ```
+    //
+    // Each stack entry has the form `{ <group name> [<folded operands>] (<remaining operands>) }`
+    // and every folded operand is a `cfg` predicate wrapped in braces. Operands which can be
+    // expressed as a `cfg` predicate are folded without any nesting. Only the operands which must
+    // be munched, such as `macro(...)`, nest the rest of the fold inside of their expansion, which
+    // is passed the constant `cfg` predicate they resolved to.
+    (
+        @__internal_single_munch
+        if all($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! { @__internal_fold [] all [] { $($yes)* } { $($no)* } $($preds)* }
+    };
+    (
+        @__internal_single_munch
+        if any($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! { @__internal_fold [] any [] { $($yes)* } { $($no)* } $($preds)* }
+    };
+
+    // Groups whose operands were all resolved by munching, e.g. because they are `macro(...)`
+    // predicates, fold to a constant. Constants short-circuit their enclosing group just like the
+    // operands they came from and select the branch to emit directly, without any `#[cfg(...)]`
+    // attribute, which keeps such predicates usable in positions which don't accept attributes.
+    (@__internal_fold $stack:tt all [$({ all() })*] $yes:tt $no:tt) => {
+        $crate::__cond! { @__internal_fold_const $stack [all()] $yes $no }
+    };
+    (@__internal_fold $stack:tt all [$({ $(all)? $(any)? () })*] $yes:tt $no:tt) => {
+        $crate::__cond! { @__internal_fold_const $stack [any()] $yes $no }
+    };
+    (@__internal_fold $stack:tt any [$({ any() })*] $yes:tt $no:tt) => {
+        $crate::__cond! { @__internal_fold_const $stack [any()] $yes $no }
+    };
+    (@__internal_fold $stack:tt any [$({ $(all)? $(any)? () })*] $yes:tt $no:tt) => {
+        $crate::__cond! { @__internal_fold_const $stack [all()] $yes $no }
+    };
+    (@__internal_fold $stack:tt not [{ all() }] $yes:tt $no:tt) => {
+        $crate::__cond! { @__internal_fold_const $stack [any()] $yes $no }
+    };
+    (@__internal_fold $stack:tt not [{ any() }] $yes:tt $no:tt) => {
+        $crate::__cond! { @__internal_fold_const $stack [all()] $yes $no }
+    };
+    (@__internal_fold_const [] [all()] { $($yes:tt)* } $no:tt) => {
+        $($yes)*
+    };
+    (@__internal_fold_const [] [any()] $yes:tt { $($no:tt)* }) => {
+        $($no)*
+    };
+    (
+        @__internal_fold_const [{ all [$($outer_folded:tt)*] $outer_rest:tt } $($stack:tt)*]
+        [any()] $yes:tt $no:tt
+    ) => {
+        $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no }
+    };
+    (
+        @__internal_fold_const [{ any [$($outer_folded:tt)*] $outer_rest:tt } $($stack:tt)*]
+        [all()] $yes:tt $no:tt
+    ) => {
+        $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no }
+    };
+    (
+        @__internal_fold_const [{ $outer:ident [$($outer_folded:tt)*] ($($outer_rest:tt)*) } $($stack:tt)*]
+        [$($constant:tt)*] $yes:tt $no:tt
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $outer [$($outer_folded)* { $($constant)* }] $yes $no
+            $($outer_rest)*
+        }
+    };
+
+    // Once the outermost group is folded, its `cfg` predicate selects the branch to emit.
+    (
+        @__internal_fold [] $(all)? $(any)? [{ $($folded:tt)* }] { $($yes:tt)* } { $($no:tt)* }
+    ) => {
+        #[cfg($($folded)*)] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg(not($($folded)*))] $crate::__cond! { @__internal_id $($no)* }
+    };
+    (
+        @__internal_fold [] $op:ident [$({ $($folded:tt)* })*] { $($yes:tt)* } { $($no:tt)* }
+    ) => {
+        #[cfg($op($($($folded)*),*))] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg(not($op($($($folded)*),*)))] $crate::__cond! { @__internal_id $($no)* }
+    };
+
+    // Finished groups are folded into the group which encloses them. Runs of groups which end
+    // together are folded at once and groups with a single operand are folded without their
+    // `all(...)` or `any(...)` wrapper.
+    (
+        @__internal_fold [
+            { $mid:ident [$({ $($mid_folded:tt)* })*] () }
+            { $outer:ident [$($outer_folded:tt)*] ($($outer_rest:tt)*) }
+            $($stack:tt)*
+        ]
+        $op:ident [$({ $($folded:tt)* })*] $yes:tt $no:tt
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $outer
+            [$($outer_folded)* { $mid($($($mid_folded)*,)* $op($($($folded)*),*)) }] $yes $no
+            $($outer_rest)*
+        }
+    };
+    (
+        @__internal_fold [{ $outer:ident [$($outer_folded:tt)*] ($($outer_rest:tt)*) } $($stack:tt)*]
+        $(all)? $(any)? [{ $($folded:tt)* }] $yes:tt $no:tt
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $outer [$($outer_folded)* { $($folded)* }] $yes $no
+            $($outer_rest)*
+        }
+    };
+    (
+        @__internal_fold [{ $outer:ident [$($outer_folded:tt)*] ($($outer_rest:tt)*) } $($stack:tt)*]
+        $op:ident [$({ $($folded:tt)* })*] $yes:tt $no:tt
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $outer
+            [$($outer_folded)* { $op($($($folded)*),*) }] $yes $no
+            $($outer_rest)*
+        }
+    };
+
+    // Groups which consist entirely of `cfg` operands are folded in one go.
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        $(cfg($($args:tt)*)),+ $(,)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* $({ $($args)* })+] $yes $no
+        }
+    };
+
+    // Operands which can be expressed as `cfg` predicates...
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        cfg($($a0:tt)*), cfg($($a1:tt)*), cfg($($a2:tt)*), cfg($($a3:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op
+            [$($folded)* { $($a0)* } { $($a1)* } { $($a2)* } { $($a3)* }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        cfg($($a0:tt)*), cfg($($a1:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { $($a0)* } { $($a1)* }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        cfg($($args:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { $($args)* }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] all [$($folded:tt)*] $yes:tt $no:tt
+        true() $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! { @__internal_fold [$($stack)*] all [$($folded)*] $yes $no $($($rest)*)? }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] any [$($folded:tt)*] $yes:tt $no:tt
+        false() $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! { @__internal_fold [$($stack)*] any [$($folded)*] $yes $no $($($rest)*)? }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] all [$($folded:tt)*] $yes:tt $no:tt
+        false() $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] any [$($folded:tt)*] $yes:tt $no:tt
+        true() $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        true() $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { all() }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        false() $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { any() }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        cfg_not($($args:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { not($($args)*) }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        cfg_eq($key:ident, $value:literal $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { $key = $value }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        feature($($name:literal),+ $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op
+            [$($folded)* { all($(feature = $name),+) }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        target_os($($name:literal),+ $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op
+            [$($folded)* { any($(target_os = $name),+) }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        rustc_at_least($version:literal $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op
+            [$($folded)* { cfgenius_rustc_at_least = $version }] $yes $no
+            $($($rest)*)?
+        }
+    };
+
+    // ...groups, which are pushed onto the stack unless they only contain `cfg` operands...
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        not(macro(! $($path:tt)*)) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)*] $yes $no
+            macro($($path)*) $(, $($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        not(macro($($path:tt)*)) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)*] $yes $no
+            macro(! $($path)*) $(, $($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        all($(cfg($($args:tt)*)),* $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { all($($($args)*),*) }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        any($(cfg($($args:tt)*)),* $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { any($($($args)*),*) }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        not($(cfg($($args:tt)*)),* $(,)?) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [$($stack)*] $op [$($folded)* { not($($($args)*),*) }] $yes $no
+            $($($rest)*)?
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        all($($inner:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [{ $op [$($folded)*] ($($($rest)*)?) } $($stack)*] all [] $yes $no
+            $($inner)*
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        any($($inner:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [{ $op [$($folded)*] ($($($rest)*)?) } $($stack)*] any [] $yes $no
+            $($inner)*
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        not($($inner:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [{ $op [$($folded)*] ($($($rest)*)?) } $($stack)*] not [] $yes $no
+            $($inner)*
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        ($($inner:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_fold [{ $op [$($folded)*] ($($($rest)*)?) } $($stack)*] all [] $yes $no
+            $($inner)*
+        }
+    };
+
+    // ...and operands which must be munched, which continue the fold in both of their branches.
+    // Variables are invoked directly to save a level of expansion. Once an operand decides the
+    // result of its group, the remaining operands of the group are skipped, and operands which
+    // cannot affect the result are dropped.
+    //
+    // Since every variable nests the rest of the fold inside of its expansion, each variable which
+    // is evaluated costs at least one level of the recursion limit, however the operands are
+    // split up. To keep it at about one level rather than two, runs of plain variables are invoked
+    // sixteen at a time, each within the branch of the one before it, and the fold is only resumed
+    // after the last of them.
+    (
+        @__internal_fold [$($stack:tt)*] all [$($folded:tt)*] $yes:tt $no:tt
+        macro($($f0:ident)? $(:: $s0:ident)* $( => $($a0:tt)*)?),
+        macro($($f1:ident)? $(:: $s1:ident)* $( => $($a1:tt)*)?),
+        macro($($f2:ident)? $(:: $s2:ident)* $( => $($a2:tt)*)?),
+        macro($($f3:ident)? $(:: $s3:ident)* $( => $($a3:tt)*)?),
+        macro($($f4:ident)? $(:: $s4:ident)* $( => $($a4:tt)*)?),
+        macro($($f5:ident)? $(:: $s5:ident)* $( => $($a5:tt)*)?),
+        macro($($f6:ident)? $(:: $s6:ident)* $( => $($a6:tt)*)?),
+        macro($($f7:ident)? $(:: $s7:ident)* $( => $($a7:tt)*)?),
+        macro($($f8:ident)? $(:: $s8:ident)* $( => $($a8:tt)*)?),
+        macro($($f9:ident)? $(:: $s9:ident)* $( => $($a9:tt)*)?),
+        macro($($f10:ident)? $(:: $s10:ident)* $( => $($a10:tt)*)?),
+        macro($($f11:ident)? $(:: $s11:ident)* $( => $($a11:tt)*)?),
+        macro($($f12:ident)? $(:: $s12:ident)* $( => $($a12:tt)*)?),
+        macro($($f13:ident)? $(:: $s13:ident)* $( => $($a13:tt)*)?),
+        macro($($f14:ident)? $(:: $s14:ident)* $( => $($a14:tt)*)?),
+        macro($($f15:ident)? $(:: $s15:ident)* $( => $($a15:tt)*)?)
+        $(, $($rest:tt)*)?
+    ) => {
+        $($f0)? $(:: $s0)*! { $(args { $($a0)* })? yes {
+        $($f1)? $(:: $s1)*! { $(args { $($a1)* })? yes {
+        $($f2)? $(:: $s2)*! { $(args { $($a2)* })? yes {
+        $($f3)? $(:: $s3)*! { $(args { $($a3)* })? yes {
+        $($f4)? $(:: $s4)*! { $(args { $($a4)* })? yes {
+        $($f5)? $(:: $s5)*! { $(args { $($a5)* })? yes {
+        $($f6)? $(:: $s6)*! { $(args { $($a6)* })? yes {
+        $($f7)? $(:: $s7)*! { $(args { $($a7)* })? yes {
+        $($f8)? $(:: $s8)*! { $(args { $($a8)* })? yes {
+        $($f9)? $(:: $s9)*! { $(args { $($a9)* })? yes {
+        $($f10)? $(:: $s10)*! { $(args { $($a10)* })? yes {
+        $($f11)? $(:: $s11)*! { $(args { $($a11)* })? yes {
+        $($f12)? $(:: $s12)*! { $(args { $($a12)* })? yes {
+        $($f13)? $(:: $s13)*! { $(args { $($a13)* })? yes {
+        $($f14)? $(:: $s14)*! { $(args { $($a14)* })? yes {
+        $($f15)? $(:: $s15)*! { $(args { $($a15)* })? yes {
+            $crate::__cond! {
+                @__internal_fold [$($stack)*] all [$($folded)*] $yes $no
+                $($($rest)*)?
+            }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+        } no { $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no } } }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] any [$($folded:tt)*] $yes:tt $no:tt
+        macro($($f0:ident)? $(:: $s0:ident)* $( => $($a0:tt)*)?),
+        macro($($f1:ident)? $(:: $s1:ident)* $( => $($a1:tt)*)?),
+        macro($($f2:ident)? $(:: $s2:ident)* $( => $($a2:tt)*)?),
+        macro($($f3:ident)? $(:: $s3:ident)* $( => $($a3:tt)*)?),
+        macro($($f4:ident)? $(:: $s4:ident)* $( => $($a4:tt)*)?),
+        macro($($f5:ident)? $(:: $s5:ident)* $( => $($a5:tt)*)?),
+        macro($($f6:ident)? $(:: $s6:ident)* $( => $($a6:tt)*)?),
+        macro($($f7:ident)? $(:: $s7:ident)* $( => $($a7:tt)*)?),
+        macro($($f8:ident)? $(:: $s8:ident)* $( => $($a8:tt)*)?),
+        macro($($f9:ident)? $(:: $s9:ident)* $( => $($a9:tt)*)?),
+        macro($($f10:ident)? $(:: $s10:ident)* $( => $($a10:tt)*)?),
+        macro($($f11:ident)? $(:: $s11:ident)* $( => $($a11:tt)*)?),
+        macro($($f12:ident)? $(:: $s12:ident)* $( => $($a12:tt)*)?),
+        macro($($f13:ident)? $(:: $s13:ident)* $( => $($a13:tt)*)?),
+        macro($($f14:ident)? $(:: $s14:ident)* $( => $($a14:tt)*)?),
+        macro($($f15:ident)? $(:: $s15:ident)* $( => $($a15:tt)*)?)
+        $(, $($rest:tt)*)?
+    ) => {
+        $($f0)? $(:: $s0)*! {
+            $(args { $($a0)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f1)? $(:: $s1)*! {
+            $(args { $($a1)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f2)? $(:: $s2)*! {
+            $(args { $($a2)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f3)? $(:: $s3)*! {
+            $(args { $($a3)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f4)? $(:: $s4)*! {
+            $(args { $($a4)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f5)? $(:: $s5)*! {
+            $(args { $($a5)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f6)? $(:: $s6)*! {
+            $(args { $($a6)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f7)? $(:: $s7)*! {
+            $(args { $($a7)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f8)? $(:: $s8)*! {
+            $(args { $($a8)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f9)? $(:: $s9)*! {
+            $(args { $($a9)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f10)? $(:: $s10)*! {
+            $(args { $($a10)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f11)? $(:: $s11)*! {
+            $(args { $($a11)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f12)? $(:: $s12)*! {
+            $(args { $($a12)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f13)? $(:: $s13)*! {
+            $(args { $($a13)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f14)? $(:: $s14)*! {
+            $(args { $($a14)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+        $($f15)? $(:: $s15)*! {
+            $(args { $($a15)* })?
+            yes { $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no } }
+            no {
+            $crate::__cond! {
+                @__internal_fold [$($stack)*] any [$($folded)*] $yes $no
+                $($($rest)*)?
+            }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+        } }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] all [$($folded:tt)*] $yes:tt $no:tt
+        macro($($first:ident)? $(:: $segment:ident)* $( => $($args:tt)*)?) $(, $($rest:tt)*)?
+    ) => {
+        $($first)? $(:: $segment)*! {
+            $(args { $($args)* })?
+            yes {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] all [$($folded)*] $yes $no
+                    $($($rest)*)?
+                }
+            }
+            no {
+                $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] all [$($folded:tt)*] $yes:tt $no:tt
+        macro(! $($first:ident)? $(:: $segment:ident)* $( => $($args:tt)*)?) $(, $($rest:tt)*)?
+    ) => {
+        $($first)? $(:: $segment)*! {
+            $(args { $($args)* })?
+            yes {
+                $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no }
+            }
+            no {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] all [$($folded)*] $yes $no
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] any [$($folded:tt)*] $yes:tt $no:tt
+        macro($($first:ident)? $(:: $segment:ident)* $( => $($args:tt)*)?) $(, $($rest:tt)*)?
+    ) => {
+        $($first)? $(:: $segment)*! {
+            $(args { $($args)* })?
+            yes {
+                $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no }
+            }
+            no {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] any [$($folded)*] $yes $no
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] any [$($folded:tt)*] $yes:tt $no:tt
+        macro(! $($first:ident)? $(:: $segment:ident)* $( => $($args:tt)*)?) $(, $($rest:tt)*)?
+    ) => {
+        $($first)? $(:: $segment)*! {
+            $(args { $($args)* })?
+            yes {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] any [$($folded)*] $yes $no
+                    $($($rest)*)?
+                }
+            }
+            no {
+                $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        macro($($first:ident)? $(:: $segment:ident)* $( => $($args:tt)*)?) $(, $($rest:tt)*)?
+    ) => {
+        $($first)? $(:: $segment)*! {
+            $(args { $($args)* })?
+            yes {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] $op [$($folded)* { all() }] $yes $no
+                    $($($rest)*)?
+                }
+            }
+            no {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] $op [$($folded)* { any() }] $yes $no
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        macro(! $($first:ident)? $(:: $segment:ident)* $( => $($args:tt)*)?) $(, $($rest:tt)*)?
+    ) => {
+        $($first)? $(:: $segment)*! {
+            $(args { $($args)* })?
+            yes {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] $op [$($folded)* { any() }] $yes $no
+                    $($($rest)*)?
+                }
+            }
+            no {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] $op [$($folded)* { all() }] $yes $no
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] all [$($folded:tt)*] $yes:tt $no:tt
+        $pred:ident ($($pred_args:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $pred($($pred_args)*) {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] all [$($folded)*] $yes $no
+                    $($($rest)*)?
+                }
+            } else {
+                $crate::__cond! { @__internal_fold [$($stack)*] all [{ any() }] $yes $no }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] any [$($folded:tt)*] $yes:tt $no:tt
+        $pred:ident ($($pred_args:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $pred($($pred_args)*) {
+                $crate::__cond! { @__internal_fold [$($stack)*] any [{ all() }] $yes $no }
+            } else {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] any [$($folded)*] $yes $no
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+    (
+        @__internal_fold [$($stack:tt)*] $op:ident [$($folded:tt)*] $yes:tt $no:tt
+        $pred:ident ($($pred_args:tt)*) $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $pred($($pred_args)*) {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] $op [$($folded)* { all() }] $yes $no
+                    $($($rest)*)?
+                }
+            } else {
+                $crate::__cond! {
+                    @__internal_fold [$($stack)*] $op [$($folded)* { any() }] $yes $no
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+
+    // nor and none_of
+    (
+        @__internal_single_munch
+        if none_of($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if nor($($preds)*) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if nor($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if any($($preds)*) {
+                $($no)*
+            } else {
+                $($yes)*
+            }
+        }
+    };
+
+    // nand
+    (
+        @__internal_single_munch
+        if nand($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if all($($preds)*) {
+                $($no)*
+            } else {
+                $($yes)*
+            }
+        }
+    };
+
+    // xor
+    (
+        @__internal_single_munch
+        if xor(
+            $($left_pred:ident)?($($left_args:tt)*),
+            $($right_pred:ident)?($($right_args:tt)*) $(,)?
+        ) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($left_pred)?($($left_args)*) {
+                $crate::__cond! {
+                    @__internal_single_munch
+                    if $($right_pred)?($($right_args)*) {
+                        $($no)*
+                    } else {
+                        $($yes)*
+                    }
+                }
+            } else {
+                $crate::__cond! {
+                    @__internal_single_munch
+                    if $($right_pred)?($($right_args)*) {
+                        $($yes)*
+                    } else {
+                        $($no)*
+                    }
+                }
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if xor($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`xor` expects exactly two predicates");
+    };
+
+    // implies
+    (
+        @__internal_single_munch
+        if implies(
+            $($left_pred:ident)?($($left_args:tt)*),
+            $($right_pred:ident)?($($right_args:tt)*) $(,)?
+        ) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if any(not($($left_pred)?($($left_args)*)), $($right_pred)?($($right_args)*)) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if implies($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`implies` expects exactly two predicates");
+    };
+
+    // iff
+    (
+        @__internal_single_munch
+        if iff(
+            $($left_pred:ident)?($($left_args:tt)*),
+            $($right_pred:ident)?($($right_args:tt)*) $(,)?
+        ) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if xor(
+                $($left_pred)?($($left_args)*),
+                $($right_pred)?($($right_args)*),
+            ) {
+                $($no)*
+            } else {
+                $($yes)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if iff($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`iff` expects exactly two predicates");
+    };
+
+    // between
+    (
+        @__internal_single_munch
+        if between($low:tt, $high:tt, $key:ident $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_numerals $high []
+            @__internal_between $low $key {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if between($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(
+            "`between` expects a lower bound, an upper bound, and a cfg key, e.g. `between(1, 4, level)`"
+        );
+    };
+
+    // This table builds the list of string literals from `"0"` up to and including the provided
+    // integer literal before forwarding it to the `@<continuation>` muncher.
+    (@__internal_numerals 0 [$($acc:tt)*] @$cont:ident $($rest:tt)*) => {
+        $crate::__cond! { @$cont ["0" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 32 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 31 ["32" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 31 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 30 ["31" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 30 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 29 ["30" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 29 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 28 ["29" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 28 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 27 ["28" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 27 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 26 ["27" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 26 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 25 ["26" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 25 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 24 ["25" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 24 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 23 ["24" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 23 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 22 ["23" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 22 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 21 ["22" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 21 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 20 ["21" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 20 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 19 ["20" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 19 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 18 ["19" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 18 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 17 ["18" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 17 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 16 ["17" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 16 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 15 ["16" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 15 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 14 ["15" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 14 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 13 ["14" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 13 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 12 ["13" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 12 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 11 ["12" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 11 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 10 ["11" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 10 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 9 ["10" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 9 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 8 ["9" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 8 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 7 ["8" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 7 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 6 ["7" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 6 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 5 ["6" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 5 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 4 ["5" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 4 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 3 ["4" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 3 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 2 ["3" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 2 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 1 ["2" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals 1 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_numerals 0 ["1" $($acc)*] $($rest)* }
+    };
+    (@__internal_numerals $value:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected an integer literal between 0 and 32, found `",
+            ::core::stringify!($value),
+            "`",
+        ));
+    };
+
+    // The values up to `low` are then listed again and used as a counter for the number of values
+    // to drop from the front of the list. The counter has one more entry than the number of values
+    // to drop since it includes `low` itself.
+    (
+        @__internal_between [$($values:literal)*] $low:tt $key:ident {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_numerals $low []
+            @__internal_between_drop [$($values)*] $key {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_between_drop [$_low:tt] [$($values:literal)*] $key:ident {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if cfg(any($($key = $values),*)) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_between_drop
+        [$_a:tt $_b:tt $_c:tt $_d:tt $($counter:tt)+]
+        [$_w:tt $_x:tt $_y:tt $_z:tt $($values:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__cond! { @__internal_between_drop [$($counter)+] [$($values)*] $($rest)* }
+    };
+    (
+        @__internal_between_drop
+        [$_a:tt $($counter:tt)+]
+        [$_w:tt $($values:tt)*]
+        $($rest:tt)*
+    ) => {
+        $crate::__cond! { @__internal_between_drop [$($counter)+] [$($values)*] $($rest)* }
+    };
+    (
+        @__internal_between_drop [$($counter:tt)*] [] $key:ident {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($no)*
+    };
+
+    // at_least
+    (
+        @__internal_single_munch
+        if at_least($count:tt $(, $($preds:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_unary $count []
+            @__internal_at_least ($($($preds)*)?) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+
+    // exactly
+    (
+        @__internal_single_munch
+        if exactly($count:tt $(, $($preds:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_unary $count []
+            @__internal_exactly ($($($preds)*)?) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+
+    // one_of
+    (
+        @__internal_single_munch
+        if one_of($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if exactly(1, $($preds)*) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+
+    // at_most
+    (
+        @__internal_single_munch
+        if at_most($count:tt $(, $($preds:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_unary $count [_]
+            @__internal_at_least ($($($preds)*)?) {
+                $($no)*
+            } else {
+                $($yes)*
+            }
+        }
+    };
+
+    // majority
+    //
+    // Each operand is turned into a unit of the operand count, which is then halved to find the
+    // threshold.
+    (
+        @__internal_single_munch
+        if majority($($($pred:ident)? ($($pred_args:tt)*)),* $(,)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_majority [$([$($pred_args)*])*] []
+            ($($($pred)? ($($pred_args)*)),*) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_single_munch
+        if majority($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!("`majority` expects a comma-separated list of predicates");
+    };
+    (@__internal_majority [$first:tt $second:tt $($units:tt)*] [$($half:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_majority [$($units)*] [$($half)* _] $($rest)* }
+    };
+    (@__internal_majority [$($unit:tt)?] [$($half:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_at_least [$($half)* _] $($rest)* }
+    };
+
+    // Counts are threaded through the munchers below in unary, with one token per remaining unit.
+    // This table converts the integer literal provided by the user into that representation before
+    // forwarding it to the `@<continuation>` muncher.
+    (@__internal_unary 0 [$($acc:tt)*] @$cont:ident $($rest:tt)*) => {
+        $crate::__cond! { @$cont [$($acc)*] $($rest)* }
+    };
+    (@__internal_unary 32 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 31 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 31 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 30 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 30 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 29 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 29 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 28 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 28 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 27 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 27 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 26 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 26 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 25 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 25 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 24 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 24 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 23 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 23 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 22 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 22 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 21 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 21 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 20 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 20 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 19 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 19 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 18 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 18 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 17 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 17 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 16 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 16 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 15 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 15 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 14 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 14 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 13 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 13 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 12 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 12 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 11 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 11 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 10 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 10 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 9 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 9 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 8 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 8 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 7 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 7 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 6 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 6 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 5 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 5 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 4 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 4 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 3 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 3 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 2 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 2 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 1 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary 1 [$($acc:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_unary 0 [$($acc)* _] $($rest)* }
+    };
+    (@__internal_unary $count:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected an integer literal between 0 and 32, found `",
+            ::core::stringify!($count),
+            "`",
+        ));
+    };
+
+    (
+        @__internal_at_least [] ($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($yes)*
+    };
+    (
+        @__internal_at_least [$($need:tt)+] () {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($no)*
+    };
+    (
+        @__internal_at_least [$first_need:tt $($need:tt)*] (
+            $($first_pred:ident)?($($first_args:tt)*)
+            $(, $($rest:tt)*)?
+        ) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($first_pred)?($($first_args)*) {
+                $crate::__cond! {
+                    @__internal_at_least [$($need)*] ($($($rest)*)?) {
+                        $($yes)*
+                    } else {
+                        $($no)*
+                    }
+                }
+            } else {
+                $crate::__cond! {
+                    @__internal_at_least [$first_need $($need)*] ($($($rest)*)?) {
+                        $($yes)*
+                    } else {
+                        $($no)*
+                    }
+                }
+            }
+        }
+    };
+
+    // Once enough predicates have succeeded, the remaining predicates must all fail.
+    (
+        @__internal_exactly [] () {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($yes)*
+    };
+    (
+        @__internal_exactly [$($need:tt)+] () {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($no)*
+    };
+    (
+        @__internal_exactly [] ($($preds:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if any($($preds)*) {
+                $($no)*
+            } else {
+                $($yes)*
+            }
+        }
+    };
+    (
+        @__internal_exactly [$first_need:tt $($need:tt)*] (
+            $($first_pred:ident)?($($first_args:tt)*)
+            $(, $($rest:tt)*)?
+        ) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($first_pred)?($($first_args)*) {
+                $crate::__cond! {
+                    @__internal_exactly [$($need)*] ($($($rest)*)?) {
+                        $($yes)*
+                    } else {
+                        $($no)*
+                    }
+                }
+            } else {
+                $crate::__cond! {
+                    @__internal_exactly [$first_need $($need)*] ($($($rest)*)?) {
+                        $($yes)*
+                    } else {
+                        $($no)*
+                    }
+                }
+            }
+        }
+    };
+
+    // macro
+    //
+    // The path is matched segment by segment rather than as a `path` fragment since an opaque
+    // `path` followed by `!` is parsed as a path expression when expanded in expression position.
+    (
+        @__internal_single_munch
+        if macro($($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($first)? $(:: $rest)*! { $(args { $($args)* })? yes { $($yes)* } no { $($no)* } }
+    };
+    (
+        @__internal_single_munch
+        if macro(! $($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($first)? $(:: $rest)*! { $(args { $($args)* })? yes { $($no)* } no { $($yes)* } }
+    };
+
+    // all_spliced and any_spliced
+    //
+    // The list macro expands to an invocation of `__cond!` which continues at
+    // `@__internal_spliced` with the predicates it produced.
+    (
+        @__internal_single_munch
+        if all_spliced($($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($first)? $(:: $rest)*! {
+            $(args { $($args)* })?
+            callback { $crate::__cond }
+            splice { @__internal_spliced all { $($yes)* } else { $($no)* } }
+        }
+    };
+    (
+        @__internal_single_munch
+        if any_spliced($($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        $($first)? $(:: $rest)*! {
+            $(args { $($args)* })?
+            callback { $crate::__cond }
+            splice { @__internal_spliced any { $($yes)* } else { $($no)* } }
+        }
+    };
+    (
+        @__internal_spliced $op:ident {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+        $($preds:tt)*
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $op($($preds)*) {
+                $($yes)*
+            } else {
+                $($no)*
+            }
+        }
+    };
+
+    // env and env_eq
+    (
+        @__internal_single_munch
+        if env($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(
+            "environment variables cannot be read by cfgenius predicates; forward them from a build \
+             script using `cargo::rustc-cfg` and check them with `cfg(...)` instead"
+        );
+    };
+    (
+        @__internal_single_munch
+        if env_eq($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(
+            "environment variables cannot be read by cfgenius predicates; forward them from a build \
+             script using `cargo::rustc-cfg` and check them with `cfg(...)` instead"
+        );
+    };
+
+    // const
+    (
+        @__internal_single_munch
+        if const($($args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(
+            "`const` expressions cannot be evaluated by cfgenius predicates since they are only \
+             known after macro expansion; use a regular `if` on the constant instead"
+        );
+    };
+
+    // unknown predicates
+    (
+        @__internal_single_munch
+        if $pred:ident ($($pred_args:tt)*) {
+            $($yes:tt)*
+        } else {
+            $($no:tt)*
+        }
+    ) => {
+        ::core::compile_error!(::core::concat!(
+            "unknown cfgenius predicate: `",
+            ::core::stringify!($pred),
+            "`",
+        ));
+    };
+
+    // Predicates nested in branches which are never taken are never munched so we walk through the
+    // entire predicate tree up-front to ensure that it is well-formed.
+    (@__internal_validate true()) => {};
+    (@__internal_validate false()) => {};
+    (@__internal_validate cfg($($args:tt)*)) => {};
+    (@__internal_validate cfg_not($($args:tt)*)) => {};
+    (@__internal_validate cfg_eq($key:ident, $value:literal $(,)?)) => {};
+    (@__internal_validate feature($($name:literal),+ $(,)?)) => {};
+    (@__internal_validate target_os($($name:literal),+ $(,)?)) => {};
+    (@__internal_validate rustc_at_least($version:literal $(,)?)) => {};
+    (@__internal_validate ptr_width_eq($width:tt $(,)?)) => {
+        $crate::__cond! { @__internal_ptr_widths $width @__internal_validated }
+    };
+    (@__internal_validate ptr_width_at_least($width:tt $(,)?)) => {
+        $crate::__cond! { @__internal_ptr_widths $width @__internal_validated }
+    };
+    (@__internal_validate macro($(!)? $($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?)) => {};
+    (@__internal_validate all_spliced($($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?)) => {};
+    (@__internal_validate any_spliced($($first:ident)? $(:: $rest:ident)* $( => $($args:tt)*)?)) => {};
+    (@__internal_validate ($($pred:ident)? ($($pred_args:tt)*))) => {
+        $crate::__cond! { @__internal_validate $($pred)? ($($pred_args)*) }
+    };
+    (@__internal_validate not($($pred:ident)? ($($pred_args:tt)*))) => {
+        $crate::__cond! { @__internal_validate $($pred)? ($($pred_args)*) }
+    };
+    (@__internal_validate all($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_validate any($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_validate majority($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_validate one_of($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_validate nor($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_validate none_of($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_validate nand($($preds:tt)*)) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (
+        @__internal_validate xor(
+            $($left_pred:ident)?($($left_args:tt)*),
+            $($right_pred:ident)?($($right_args:tt)*) $(,)?
+        )
+    ) => {
+        $crate::__cond! { @__internal_validate $($left_pred)?($($left_args)*) }
+        $crate::__cond! { @__internal_validate $($right_pred)?($($right_args)*) }
+    };
+    (
+        @__internal_validate implies(
+            $($left_pred:ident)?($($left_args:tt)*),
+            $($right_pred:ident)?($($right_args:tt)*) $(,)?
+        )
+    ) => {
+        $crate::__cond! { @__internal_validate $($left_pred)?($($left_args)*) }
+        $crate::__cond! { @__internal_validate $($right_pred)?($($right_args)*) }
+    };
+    (
+        @__internal_validate iff(
+            $($left_pred:ident)?($($left_args:tt)*),
+            $($right_pred:ident)?($($right_args:tt)*) $(,)?
+        )
+    ) => {
+        $crate::__cond! { @__internal_validate $($left_pred)?($($left_args)*) }
+        $crate::__cond! { @__internal_validate $($right_pred)?($($right_args)*) }
+    };
+    (@__internal_validate between($low:tt, $high:tt, $key:ident $(,)?)) => {
+        $crate::__cond! { @__internal_unary $low [] @__internal_validated }
+        $crate::__cond! { @__internal_unary $high [] @__internal_validated }
+    };
+    (@__internal_validate at_least($count:tt $(, $($preds:tt)*)?)) => {
+        $crate::__cond! { @__internal_unary $count [] @__internal_validated }
+        $crate::__cond! { @__internal_validate_list $($($preds)*)? }
+    };
+    (@__internal_validate at_most($count:tt $(, $($preds:tt)*)?)) => {
+        $crate::__cond! { @__internal_unary $count [] @__internal_validated }
+        $crate::__cond! { @__internal_validate_list $($($preds)*)? }
+    };
+    (@__internal_validate exactly($count:tt $(, $($preds:tt)*)?)) => {
+        $crate::__cond! { @__internal_unary $count [] @__internal_validated }
+        $crate::__cond! { @__internal_validate_list $($($preds)*)? }
+    };
+
+    // If we've gotten here, the predicate is either malformed or unknown. Most predicates already
+    // report both of these cases while being munched so we defer to them.
+    (@__internal_validate true($($args:tt)+)) => {
+        ::core::compile_error!("`true` expects no arguments");
+    };
+    (@__internal_validate false($($args:tt)+)) => {
+        ::core::compile_error!("`false` expects no arguments");
+    };
+    (@__internal_validate not($keyword:ident $(,)?)) => {
+        $crate::__cond! { @__internal_bare_keyword $keyword }
+    };
+    (@__internal_validate not($key:ident = $value:literal $(,)?)) => {
+        $crate::__cond! { @__internal_bare_cfg $key = $value }
+    };
+    (@__internal_validate not($($args:tt)*)) => {
+        ::core::compile_error!("`not` expects exactly one predicate");
+    };
+    (@__internal_validate $($pred:ident)? ($($pred_args:tt)*)) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($pred)? ($($pred_args)*) {} else {}
+        }
+    };
+
+    (@__internal_validated [$($tokens:tt)*]) => {};
+
+    (@__internal_validate_list) => {};
+    (
+        @__internal_validate_list
+        $($($pred:ident)? ($($pred_args:tt)*)),+ $(,)?
+    ) => {
+        $($crate::__cond! { @__internal_validate $($pred)? ($($pred_args)*) })+
+    };
+    (
+        @__internal_validate_list
+        $($first_pred:ident)? ($($first_args:tt)*)
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__cond! { @__internal_validate $($first_pred)? ($($first_args)*) }
+        $crate::__cond! { @__internal_validate_list $($($rest)*)? }
+    };
+    // Bare cfg names and key-value pairs are a common mistake so we suggest wrapping them.
+    (@__internal_validate_list true $(, $($rest:tt)*)?) => {
+        $crate::__cond! { @__internal_bare_keyword true }
+    };
+    (@__internal_validate_list false $(, $($rest:tt)*)?) => {
+        $crate::__cond! { @__internal_bare_keyword false }
+    };
+    (@__internal_validate_list $key:ident = $value:literal $(, $($rest:tt)*)?) => {
+        $crate::__cond! { @__internal_bare_cfg $key = $value }
+    };
+    (@__internal_validate_list $name:ident $(, $($rest:tt)*)?) => {
+        $crate::__cond! { @__internal_bare_cfg $name }
+    };
+    (@__internal_validate_list $first:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a predicate, found `",
+            ::core::stringify!($first),
+            "`",
+        ));
+    };
+    (@__internal_bare_keyword true) => {
+        ::core::compile_error!(
+            "expected `true()`, found `true`; the keyword can only be used as the predicate of a \
+            branch or within the block forms of `all` and `any`"
+        );
+    };
+    (@__internal_bare_keyword false) => {
+        ::core::compile_error!(
+            "expected `false()`, found `false`; the keyword can only be used as the predicate of a \
+            branch or within the block forms of `all` and `any`"
+        );
+    };
+    (@__internal_bare_keyword $name:ident) => {
+        $crate::__cond! { @__internal_bare_cfg $name }
+    };
+    (@__internal_bare_cfg $($cfg:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a predicate like `cfg(",
+            ::core::stringify!($($cfg)*),
+            ")`, found `",
+            ::core::stringify!($($cfg)*),
+            "`",
+        ));
+    };
+
+    // Now, we can implement support for an arbitrary chaining of these.
+
+    // Because falsy paths are never expanded into the final output, bad macro calls to `cond!` are
+    // ignored in the falsy paths, which is a bit janky. We avoid this scenario by validating the
+    // syntax of every branch, including the predicates nested within them, before munching through
+    // it. Each body is emitted through `@__internal_id` so that bodies consisting solely of
+    // attributes are dropped regardless of how the predicate is resolved.
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $($crate::__cond! { @__internal_validate $($pred)? ($($pred_args)*) })*
+
+        $crate::__cond! {
+            @__internal_chained_munch
+            $(
+                if $($pred)?($($pred_args)*) {
+                    $crate::__cond! { @__internal_id $($yes)* }
+                }
+            ) else + $(else {
+                $crate::__cond! { @__internal_id $($no)* }
+            })?
+        }
+    };
+
+    // Branches whose predicates are separated by `|` are lowered to `any(...)`.
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $crate::__cond! {
+            $(if any($($pred)? ($($pred_args)*) $(, $($alt_pred)? ($($alt_args)*))*) {
+                $($yes)*
+            }) else + $(else {
+                $($no)*
+            })?
+        }
+    };
+
+    // A trailing block without a predicate is an `else` branch whose `else` keyword was omitted.
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            $(if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                $($yes)*
+            }) else + else {
+                $($no)*
+            }
+        }
+    };
+
+    // Every nested `$crate::__cond!` call counts towards the recursion limit so we take care to
+    // munch the common branch kinds with a single level of expansion each. Branches gated by a
+    // plain or negated `cfg` are lowered to a pair of attributes, with the remainder of the chain
+    // hanging off of the falsy attribute. Constant predicates skip straight to the branch they select.
+    (
+        @__internal_chained_munch
+        if cfg($($args:tt)*) {
+            $($yes:tt)*
+        } else $($rest:tt)*
+    ) => {
+        #[cfg($($args)*)] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_chained_munch $($rest)* }
+    };
+    (
+        @__internal_chained_munch
+        if cfg($($args:tt)*) {
+            $($yes:tt)*
+        }
+    ) => {
+        #[cfg($($args)*)] $crate::__cond! { @__internal_id $($yes)* }
+    };
+    (
+        @__internal_chained_munch
+        if not(cfg($($args:tt)*)) {
+            $($yes:tt)*
+        } else $($rest:tt)*
+    ) => {
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg($($args)*)] $crate::__cond! { @__internal_chained_munch $($rest)* }
+    };
+    (
+        @__internal_chained_munch
+        if not(cfg($($args:tt)*)) {
+            $($yes:tt)*
+        }
+    ) => {
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($yes)* }
+    };
+    (
+        @__internal_chained_munch
+        if cfg_not($($args:tt)*) {
+            $($yes:tt)*
+        } else $($rest:tt)*
+    ) => {
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($yes)* }
+        #[cfg($($args)*)] $crate::__cond! { @__internal_chained_munch $($rest)* }
+    };
+    (
+        @__internal_chained_munch
+        if cfg_not($($args:tt)*) {
+            $($yes:tt)*
+        }
+    ) => {
+        #[cfg(not($($args)*))] $crate::__cond! { @__internal_id $($yes)* }
+    };
+    (
+        @__internal_chained_munch
+        if true() {
+            $($yes:tt)*
+        } $(else $($rest:tt)*)?
+    ) => {
+        $($yes)*
+    };
+    (
+        @__internal_chained_munch
+        if false() {
+            $($yes:tt)*
+        } $(else $($rest:tt)*)?
+    ) => {
+        $($crate::__cond! { @__internal_chained_munch $($rest)* })?
+    };
+    (
+        @__internal_chained_munch
+        if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        } $(else $($rest:tt)*)?
+    ) => {
+        $crate::__cond! {
+            @__internal_single_munch
+            if $($pred)?($($pred_args)*) {
+                $($yes)*
+            } else {
+                $($crate::__cond! {
+                    @__internal_chained_munch
+                    $($rest)*
+                })?
+            }
+        }
+    };
+    (
+        @__internal_chained_munch
+        { $($rest:tt)* }
+    ) => {
+        $($rest)*
+    };
+
+    // Finally, branches whose predicates use the block forms of `all` and `any` are rewritten into
+    // their regular forms. We only do this once the regular grammar fails to match since it walks
+    // through every token of the predicates, which uses up a lot of the recursion limit.
+    //
+    // The walker keeps the rewritten tokens of the innermost group in its first bracket and a stack
+    // of the enclosing groups in its second bracket. Each stack entry records the predicate name
+    // of the group followed by the tokens which preceded it. The bodies of the branches are copied
+    // over as-is.
+    //
+    // Other macros taking a chain can reuse the walker by placing the macro to invoke with the
+    // rewritten chain and the tokens to put in front of it at the bottom of the stack.
+    (@__internal_blocks [$($top:tt)*] []) => {
+        $crate::__cond! { @__internal_blocks_done $($top)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [[$($macro:tt)*] [$($prefix:tt)*]]) => {
+        $($macro)* { $($prefix)* $($top)* }
+    };
+    (
+        @__internal_blocks [$($top:tt)*] [{[$($name:tt)*] $($acc:tt)*} $($stack:tt)*]
+        @__internal_pop $($rest:tt)*
+    ) => {
+        $crate::__cond! {
+            @__internal_blocks [$($acc)* $($name)* ($($top)*)] [$($stack)*] $($rest)*
+        }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] all { $($inner:tt)* } $($rest:tt)*) => {
+        $crate::__cond! {
+            @__internal_blocks [] [{[all] $($top)*} $($stack)*] $($inner)* @__internal_pop $($rest)*
+        }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] any { $($inner:tt)* } $($rest:tt)*) => {
+        $crate::__cond! {
+            @__internal_blocks [] [{[any] $($top)*} $($stack)*] $($inner)* @__internal_pop $($rest)*
+        }
+    };
+    // The arguments of `cfg`, `macro`, and the spliced lists are copied over as-is since they
+    // belong to the cfg or the macro being invoked rather than to the predicate grammar.
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] cfg $args:tt $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* cfg $args] [$($stack)*] $($rest)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] macro $args:tt $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* macro $args] [$($stack)*] $($rest)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] all_spliced $args:tt $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* all_spliced $args] [$($stack)*] $($rest)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] any_spliced $args:tt $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* any_spliced $args] [$($stack)*] $($rest)* }
+    };
+    (
+        @__internal_blocks [$($top:tt)*] [$($stack:tt)*]
+        $($name:ident)? ($($inner:tt)*) $($rest:tt)*
+    ) => {
+        $crate::__cond! {
+            @__internal_blocks [] [{[$($name)?] $($top)*} $($stack)*]
+            $($inner)* @__internal_pop $($rest)*
+        }
+    };
+    // The keywords `true` and `false` are shorthands for `true()` and `false()`. Since branch bodies
+    // are never walked into, this only ever rewrites keywords in predicate position.
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] true $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* true()] [$($stack)*] $($rest)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] false $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* false()] [$($stack)*] $($rest)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] ; $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* ,] [$($stack)*] $($rest)* }
+    };
+    (@__internal_blocks [$($top:tt)*] [$($stack:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::__cond! { @__internal_blocks [$($top)* $token] [$($stack)*] $($rest)* }
+    };
+    (
+        @__internal_blocks_done
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $crate::__cond! {
+            $(if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                $($yes)*
+            }) else + $(else {
+                $($no)*
+            })?
+        }
+    };
+    (
+        @__internal_blocks_done
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::__cond! {
+            $(if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                $($yes)*
+            }) else + else {
+                $($no)*
+            }
+        }
+    };
+    (@__internal_blocks_done $($tokens:tt)*) => {
+        $crate::__cond! { @__internal_malformed_if [$($tokens)*] $($tokens)* }
+    };
+
+    // If the chain still doesn't match, we walk through it one branch at a time to point out where
+    // its structure breaks down. The first bracket holds the entire chain for the fallback error.
+    (
+        @__internal_malformed_if $chain:tt
+        if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }
+        $($rest:tt)*
+    ) => {
+        $crate::__cond! { @__internal_malformed_else $chain $($rest)* }
+    };
+    (
+        @__internal_malformed_if $chain:tt
+        if $($pred:ident)? ($($pred_args:tt)*) | $($rest:tt)*
+    ) => {
+        $crate::__cond! { @__internal_malformed_if $chain if $($rest)* }
+    };
+    (@__internal_malformed_if $chain:tt if $($pred:ident)? ($($pred_args:tt)*)) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a `{ ... }` body after the predicate `",
+            ::core::stringify!($($pred)?($($pred_args)*)),
+            "`",
+        ));
+    };
+    (
+        @__internal_malformed_if $chain:tt
+        if $($pred:ident)? ($($pred_args:tt)*) $($rest:tt)+
+    ) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a `{ ... }` body after the predicate `",
+            ::core::stringify!($($pred)?($($pred_args)*)),
+            "`, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (@__internal_malformed_if $chain:tt if $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a predicate after `if`, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (@__internal_malformed_if $chain:tt else $($rest:tt)*) => {
+        ::core::compile_error!(
+            "expected the chain to begin with `if <predicate> { ... }`, found `else`; an `else` \
+             branch must follow an `if` branch"
+        );
+    };
+    (@__internal_malformed_else $chain:tt else if $($rest:tt)*) => {
+        $crate::__cond! { @__internal_malformed_if $chain if $($rest)* }
+    };
+    (@__internal_malformed_else $chain:tt else { $($no:tt)* } else $($rest:tt)*) => {
+        ::core::compile_error!(
+            "found another branch after the final `else` branch; the `else` branch without a \
+             predicate must come last in the chain"
+        );
+    };
+    (@__internal_malformed_else $chain:tt else { $($no:tt)* } $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "expected the chain to end after the final `else` branch, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (@__internal_malformed_else $chain:tt else $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "expected `if <predicate> { ... }` or `{ ... }` after `else`, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (@__internal_malformed_else $chain:tt else) => {
+        ::core::compile_error!("expected `if <predicate> { ... }` or `{ ... }` after `else`");
+    };
+    (@__internal_malformed_else $chain:tt if $($rest:tt)*) => {
+        ::core::compile_error!(
+            "expected `else` between two branches; branches of a chain are joined with `else if`"
+        );
+    };
+    (@__internal_malformed_else $chain:tt { $($no:tt)* } $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "expected the chain to end after the final `{ ... }` block, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (@__internal_malformed_else $chain:tt $($rest:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "expected `else`, a final `{ ... }` block, or the end of the chain after a branch, \
+             found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (@__internal_malformed_if [$($chain:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_malformed $($chain)* }
+    };
+    (@__internal_malformed_else [$($chain:tt)*] $($rest:tt)*) => {
+        $crate::__cond! { @__internal_malformed $($chain)* }
+    };
+    (@__internal_malformed $($tokens:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a chain of `if <predicate> { ... }` branches, found `",
+            ::core::stringify!($($tokens)*),
+            "`",
+        ));
+    };
+    ($($tokens:tt)*) => {
+        $crate::__cond! { @__internal_blocks [] [] $($tokens)* }
+    };
+}
+
+/// A conditionally-compiled sequence of statements.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_stmt! {
+///     if <if predicate> {
+///         // statements and items
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // statements and items
+///     } else {                         // This is optional.
+///         // statements and items
+///     }
+/// }
+/// ```
+///
+/// This behaves like [`cond!`](crate::cond) except that a trailing expression in the matching
+/// branch is always treated as an expression statement. With `cond!`, such an expression becomes
+/// the value of the enclosing block when the macro is the last statement of that block, which can
+/// silently change the meaning of the code. Like with `cond!`, items and `let` bindings declared in
+/// the matching branch remain accessible after the macro.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// fn flush(buffer: &mut Vec<u8>) {
+///     cfgenius::cond_stmt! {
+///         if cfg(debug_assertions) {
+///             fn log(len: usize) -> usize {
+///                 len
+///             }
+///
+///             let len = buffer.len();
+///             log(len)
+///         } else {
+///             let len = 0;
+///         }
+///     }
+///
+///     buffer.truncate(len);
+/// }
+/// #
+/// # flush(&mut vec![1, 2, 3]);
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_stmt {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $($(else)? {
+            $($no:tt)*
+        })?
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_stmt {
+    // A trailing semicolon after a macro invocation in statement position turns the trailing
+    // expression of its expansion into an expression statement.
+    ($($tokens:tt)*) => {
+        $crate::cond! { $($tokens)* };
+    };
+}
+
+/// A conditionally-compiled sequence of `let` statements.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_let! {
+///     if <if predicate> {
+///         // `let` statements
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // `let` statements
+///     } else {                         // This is optional.
+///         // `let` statements
+///     }
+/// }
+/// ```
+///
+/// This behaves like [`cond_stmt!`](crate::cond_stmt) except that every branch, including those
+/// which are never taken, may only contain `let` statements, each terminated by a semicolon. This
+/// signals that the macro is only used to introduce bindings, which remain accessible after the
+/// macro. Since the bindings of the matching branch are the only ones which exist, every branch
+/// should usually bind the same names and the chain should end with an `else` branch.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// fn limits() -> (usize, usize) {
+///     cfgenius::cond_let!(if ptr_width_at_least(64) {
+///         let max_len = 1 << 32;
+///         let (chunk, _) = (4096, "large");
+///     } else {
+///         let max_len = 1 << 16;
+///         let chunk: usize = 512;
+///     });
+///
+///     (max_len, chunk)
+/// }
+///
+/// fn parse(input: &str) -> u32 {
+///     cfgenius::cond_let! {
+///         if cfg(all()) {
+///             let Ok(value) = input.parse::<u32>() else {
+///                 return 0;
+///             };
+///         } else {
+///             let value = 1;
+///         }
+///     }
+///
+///     value
+/// }
+/// #
+/// # assert_eq!(limits().0 == 1 << 32, cfg!(target_pointer_width = "64"));
+/// # assert_eq!((parse("12"), parse("x")), (12, 0));
+/// #
+/// # cfgenius::cond_let! {
+/// #     if false {
+/// #         let level = 1;
+/// #     } else if all { true; cfg(all()) } {
+/// #         let level = 2;
+/// #     } else {
+/// #         let level = 3;
+/// #     }
+/// # }
+/// #
+/// # cfgenius::cond_let! {
+/// #     if any { false; cfg(any()) } { let extra = 10; } { let extra = 20; }
+/// # }
+/// #
+/// # cfgenius::cond_let! {
+/// #     if cfg(any()) { let a = 1; } { let a = 2; }
+/// # }
+/// #
+/// # assert_eq!(a, 2);
+/// #
+/// # assert_eq!(level + extra, 22);
+/// #
+/// # cfgenius::cond_let! {
+/// #     if cfg(all()) {
+/// #         let a0: u32 = 0; let a1: u32 = 1; let a2: u32 = 2; let a3: u32 = 3;
+/// #         let a4: u32 = 4; let a5: u32 = 5; let a6: u32 = 6; let a7: u32 = 7;
+/// #         let a8: u32 = 8; let a9: u32 = 9; let a10: u32 = 10; let a11: u32 = 11;
+/// #         let a12: u32 = 12; let a13: u32 = 13; let a14: u32 = 14; let a15: u32 = 15;
+/// #         let a16: u32 = 16; let a17: u32 = 17; let a18: u32 = 18; let a19: u32 = 19;
+/// #         let a20: u32 = 20; let a21: u32 = 21; let a22: u32 = 22; let a23: u32 = 23;
+/// #         let a24: u32 = 24; let a25: u32 = 25; let a26: u32 = 26; let a27: u32 = 27;
+/// #         let a28: u32 = 28; let a29: u32 = 29; let a30: u32 = 30; let a31: u32 = 31;
+/// #         let a32: u32 = 32; let a33: u32 = 33; let a34: u32 = 34; let a35: u32 = 35;
+/// #         let a36: u32 = 36; let a37: u32 = 37; let a38: u32 = 38; let a39: u32 = 39;
+/// #     }
+/// # }
+/// #
+/// # assert_eq!(a0 + a39, 39);
+/// ```
+///
+/// Other statements and items are rejected, even in branches which are never taken:
+///
+/// ```compile_fail
+/// cfgenius::cond_let! {
+///     if false() {
+///         println!("not a binding");
+///     } else {
+///         let value = 1;
+///     }
+/// }
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_let {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $(let $($binding:tt)*;)*
+        }) else + $($(else)? {
+            $(let $($else_binding:tt)*;)*
+        })?
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_let {
+    // A pattern can't be followed by the `:` of its type annotation, which rules out matching the
+    // parts of a `let` statement. Instead, each statement is matched as a whole by the `stmt`
+    // fragment once the `let` token in front of it has been checked.
+    (@__internal_lets) => {};
+    (@__internal_lets let $($rest:tt)*) => {
+        $crate::cond_let! { @__internal_let let $($rest)* }
+    };
+    (@__internal_lets $($rest:tt)*) => {
+        $crate::cond_let! { @__internal_not_let $($rest)* }
+    };
+    (@__internal_let $stmt:stmt ;) => {};
+    (@__internal_let $stmt:stmt ; let $($rest:tt)*) => {
+        $crate::cond_let! { @__internal_let let $($rest)* }
+    };
+    (@__internal_let $stmt:stmt ; $($rest:tt)*) => {
+        $crate::cond_let! { @__internal_not_let $($rest)* }
+    };
+    (@__internal_let $stmt:stmt) => {
+        ::core::compile_error!("expected `;` after the final `let` statement");
+    };
+    (@__internal_not_let $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a `let` statement, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+    // Chains using the bare `true` and `false` keywords or the block forms of `all` and `any` only
+    // match once they have been rewritten into the regular grammar by the walker of `cond!`. The
+    // first bracket records whether this has already happened so that chains which are still
+    // malformed afterwards are left to `cond!` to report.
+    (
+        @__internal_chain $rewritten:tt
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + $($(else)? {
+            $($no:tt)*
+        })?
+    ) => {
+        $($crate::cond_let! { @__internal_lets $($yes)* })+
+        $($crate::cond_let! { @__internal_lets $($no)* })?
+
+        $crate::cond_stmt! {
+            $(if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                $($yes)*
+            }) else + $(else {
+                $($no)*
+            })?
+        }
+    };
+    (@__internal_chain [] $($tokens:tt)*) => {
+        $crate::__cond! {
+            @__internal_blocks [] [[$crate::cond_let!] [@__internal_chain [rewritten]]] $($tokens)*
+        }
+    };
+    (@__internal_chain [rewritten] $($tokens:tt)*) => {
+        $crate::cond! { $($tokens)* }
+    };
+    ($($tokens:tt)*) => {
+        $crate::cond_let! { @__internal_chain [] $($tokens)* }
+    };
+}
+
+/// A sequence of independently conditionally-compiled statements or items.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_all! {
+///     if <predicate 1> {
+///         // arbitrary tokens
+///     }
+///     if <predicate 2> {  // There can be zero or more of these.
+///         // arbitrary tokens
+///     }
+/// }
+/// ```
+///
+/// Unlike [`cond!`](crate::cond), which only emits the first branch whose predicate is truthy,
+/// this emits the contents of *every* branch whose predicate is truthy, in the order in which they
+/// were written. This makes it equivalent to a list of items which are each gated by their own
+/// `#[cfg(...)]` attribute and is useful for accumulating trait implementations. Since branches
+/// are independent of one another, there is no `else` branch. Use `cond!` with a negated
+/// predicate to emit tokens when none of them match.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// pub trait Backend {
+///     const NAME: &'static str;
+/// }
+///
+/// pub struct Native;
+///
+/// cfgenius::cond_all! {
+///     if cfg(unix) | target_os("wasi") {
+///         pub struct Posix;
+///
+///         impl Backend for Posix {
+///             const NAME: &'static str = "posix";
+///         }
+///     }
+///     if true() {
+///         impl Backend for Native {
+///             const NAME: &'static str = "native";
+///         }
+///     }
+///     if false() {
+///         impl Backend for Native {
+///             const NAME: &'static str = "unreachable";
+///         }
+///     }
+/// }
+/// #
+/// # assert_eq!(Native::NAME, "native");
+/// ```
+#[macro_export]
+macro_rules! cond_all {
+    (
+        $(
+            if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+                $($body:tt)*
+            }
+        )*
+    ) => {
+        $(
+            $crate::cond! {
+                if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                    $($body)*
+                }
+            }
+        )*
+    };
+    ($($tokens:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a sequence of `if <predicate> { ... }` branches without `else`, found `",
+            ::core::stringify!($($tokens)*),
+            "`",
+        ));
+    };
+}
+
+/// A conditionally-compiled statement or item which must match on every target.
+///
+/// ## Syntax
+///
+/// This accepts the same syntax as [`cond!`](crate::cond):
+///
+/// ```plain_text
+/// cond_exhaustive! {
+///     if <if predicate> {
+///         // arbitrary tokens
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // arbitrary tokens
+///     } else {                         // This is optional.
+///         // arbitrary tokens
+///     }
+/// }
+/// ```
+///
+/// With `cond!`, a chain without an `else` branch silently expands to nothing on targets where
+/// none of its predicates match. This macro opts into treating that case as a mistake: if no
+/// branch matches and no `else` branch was provided, compilation fails with an error. Chains with
+/// an `else` branch always match and behave exactly like they would with `cond!`.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define!(is_posix = any(cfg(unix), target_os("wasi")));
+///
+/// cfgenius::cond_exhaustive! {
+///     if macro(is_posix) {
+///         fn separator() -> char { '/' }
+///     } else if cfg(windows) {
+///         fn separator() -> char { '\\' }
+///     } else if true() {
+///         fn separator() -> char { '/' }
+///     }
+/// }
+/// #
+/// # assert!(separator() == '/' || separator() == '\\');
+/// #
+/// # fn exhaustive() -> u8 {
+/// #     cfgenius::cond_exhaustive! {
+/// #         if cfg(any()) { return 1; } else if false { return 0; } { return 3; }
+/// #     }
+/// # }
+/// #
+/// # fn exhaustive_blocks() -> u8 {
+/// #     cfgenius::cond_exhaustive! {
+/// #         if any { false; cfg(any()) } { return 1; } else if all { true } { return 2; }
+/// #     }
+/// # }
+/// #
+/// # assert_eq!((exhaustive(), exhaustive_blocks()), (3, 2));
+/// ```
+///
+/// ```compile_fail
+/// cfgenius::cond_exhaustive! {
+///     if cfg(any()) {
+///         fn separator() -> char { '/' }
+///     }
+/// }
+/// ```
+///
+/// The same goes for branches using the block forms of `all` and `any`:
+///
+/// ```compile_fail
+/// cfgenius::cond_exhaustive! {
+///     if all { cfg(any()) } {
+///         fn separator() -> char { '/' }
+///     }
+/// }
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_exhaustive {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + $($(else)? {
+            $($no:tt)*
+        })?
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_exhaustive {
+    // The chain is matched as a whole to find out whether it ends with an `else` branch. Like in
+    // `cond_let!`, chains which only match once their bare keywords and block forms have been
+    // rewritten by the walker of `cond!` are matched again afterwards, and chains which are still
+    // malformed are left to `cond!` to report.
+    (
+        @__internal_chain $rewritten:tt
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else + $(else)? {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::cond! {
+            $(if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                $($yes)*
+            }) else + else {
+                $($no)*
+            }
+        }
+    };
+    (
+        @__internal_chain $rewritten:tt
+        $(if $($pred:ident)? ($($pred_args:tt)*) $(| $($alt_pred:ident)? ($($alt_args:tt)*))* {
+            $($yes:tt)*
+        }) else +
+    ) => {
+        $crate::cond! {
+            $(if $($pred)? ($($pred_args)*) $(| $($alt_pred)? ($($alt_args)*))* {
+                $($yes)*
+            }) else + else {
+                ::core::compile_error!(
+                    "no branch of `cond_exhaustive!` matched on this target and no `else` branch \
+                     was provided"
+                );
+            }
+        }
+    };
+    (@__internal_chain [] $($tokens:tt)*) => {
+        $crate::__cond! {
+            @__internal_blocks [] [[$crate::cond_exhaustive!] [@__internal_chain [rewritten]]]
+            $($tokens)*
+        }
+    };
+    (@__internal_chain [rewritten] $($tokens:tt)*) => {
+        $crate::cond! { $($tokens)* }
+    };
+    ($($tokens:tt)*) => {
+        $crate::cond_exhaustive! { @__internal_chain [] $($tokens)* }
+    };
+}
+
+/// Conditionally-compiled statements or items selected by the value of a single cfg key.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_match! {
+///     <key> {
+///         "<value 1>" | "<value 2>" => {  // There can be one or more of these.
+///             // arbitrary tokens
+///         },
+///         _ => {                          // This is optional.
+///             // arbitrary tokens
+///         },
+///     }
+/// }
+/// ```
+///
+/// Each arm is lowered to a `cfg(any(<key> = "<value 1>", <key> = "<value 2>", ...))` branch of
+/// a [`cond!`](crate::cond) chain, in the order in which they were written, and the `_` arm
+/// becomes its `else` branch. Hence, the first arm whose value matches is emitted, which matters
+/// for keys such as `target_feature` which can have several values at once. Commas between arms
+/// are optional.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::cond_match! {
+///     target_os {
+///         "linux" | "android" => {
+///             fn family() -> &'static str { "linux" }
+///         },
+///         "windows" => {
+///             fn family() -> &'static str { "windows" }
+///         },
+///         _ => {
+///             fn family() -> &'static str { "other" }
+///         },
+///     }
+/// }
+///
+/// fn width() -> u32 {
+///     cfgenius::cond_match!(target_pointer_width {
+///         "64" => { return 64; }
+///         "32" => { return 32; }
+///         _ => { return 16; }
+///     });
+/// }
+/// #
+/// # assert_eq!(family() == "windows", cfg!(windows));
+/// # assert_eq!(width() as usize, usize::BITS as usize);
+/// ```
+#[macro_export]
+macro_rules! cond_match {
+    (
+        $key:ident {
+            $(
+                $value:literal $(| $alt_value:literal)* => {
+                    $($body:tt)*
+                } $(,)?
+            )+
+            $(
+                _ => {
+                    $($default:tt)*
+                } $(,)?
+            )?
+        }
+    ) => {
+        $crate::cond! {
+            $(if cfg(any($key = $value $(, $key = $alt_value)*)) {
+                $($body)*
+            }) else + $(else {
+                $($default)*
+            })?
+        }
+    };
+    ($($tokens:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a cfg key followed by `{ \"<value>\" => { ... }, _ => { ... } }`, found `",
+            ::core::stringify!($($tokens)*),
+            "`",
+        ));
+    };
+}
+
+/// A drop-in replacement for [`cfg_if!`][cfg_if] which accepts its exact grammar.
+///
+/// Each `#[cfg(<cfg input>)]` is lowered to the `cfg(<cfg input>)` predicate of
+/// [`cond!`](crate::cond), making migration a matter of renaming the macro. Once migrated,
+/// branches can be rewritten to use the full `cfgenius` predicate grammar incrementally.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cfg_if_compat! {
+///     if #[cfg(<cfg input>)] {
+///         // arbitrary tokens
+///     } else if #[cfg(<cfg input>)] {  // There can be zero or more of these.
+///         // arbitrary tokens
+///     } else {                         // This is optional.
+///         // arbitrary tokens
+///     }
+/// }
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::cfg_if_compat! {
+///     if #[cfg(unix)] {
+///         fn foo() -> &'static str { "unix" }
+///     } else if #[cfg(target_pointer_width = "32")] {
+///         fn foo() -> &'static str { "32-bit" }
+///     } else {
+///         fn foo() -> &'static str { "fallback" }
+///     }
+/// }
+/// #
+/// # assert_eq!(foo() == "unix", cfg!(unix));
+/// ```
+///
+/// [cfg_if]: https://docs.rs/cfg-if/1.0.0/cfg_if/macro.cfg_if.html
+#[macro_export]
+macro_rules! cfg_if_compat {
+    (
+        $(if #[cfg($($args:tt)*)] {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $crate::cond! {
+            $(if cfg($($args)*) {
+                $($yes)*
+            }) else + $(else {
+                $($no)*
+            })?
+        }
+    };
+}
+
+/// A conditionally-compiled expression.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_expr! {
+///     if <if predicate> {
+///         // arbitrary tokens forming a `BlockExpression`.
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // arbitrary tokens forming a `BlockExpression`.
+///     } else {                         // This is optional.
+///         // arbitrary tokens forming a `BlockExpression`.
+///     }
+/// }
+/// ```
+///
+/// ...or, if every branch is a single expression, the following shorter form can be used instead:
+///
+/// ```plain_text
+/// cond_expr!(
+///     if <if predicate> => <expression>
+///     else if <else-if predicate> => <expression>  // There can be zero or more of these.
+///     else <expression>                            // This is optional.
+/// )
+/// ```
+///
+/// Because each expression ends at the first `else` token which isn't nested in a group, an
+/// expression containing its own `if ... else ...` must be wrapped in parentheses or braces.
+///
+/// In both of these forms, omitting the final `else` branch makes it a compile error for none of
+/// the predicates to match on the target being compiled for.
+///
+/// ```compile_fail
+/// const VALUE: u32 = cfgenius::cond_expr!(if false() => 1);
+/// ```
+///
+/// Or, if you just want to choose between two expressions, the following ternary form can be used
+/// instead:
+///
+/// ```plain_text
+/// cond_expr!(<predicate> => <truthy expression>, <falsy expression>)
+/// ```
+///
+/// This is the preferred spelling of the ternary but, since it reads naturally for a conditional
+/// expression, the C-style spelling is accepted as well and lowers to the exact same expansion:
+///
+/// ```plain_text
+/// cond_expr!(<predicate> ? <truthy expression> : <falsy expression>)
+/// ```
+///
+/// The truthy expression ends at the first `:` which isn't nested in a group, so an expression
+/// containing its own `:`, such as a closure with annotated parameters, must be wrapped in
+/// parentheses. Paths are unaffected since `::` is a distinct token.
+///
+/// ```
+/// const ARROW: u32 = cfgenius::cond_expr!(cfg(all()) => 1, 2);
+/// const QUESTION: u32 = cfgenius::cond_expr!(cfg(all()) ? 1 : 2);
+/// const PATHS: u32 = cfgenius::cond_expr!(not(true()) ? u32::MIN : core::primitive::u32::MAX);
+///
+/// let add = cfgenius::cond_expr!(true() ? (|a: u32, b: u32| a + b) : |a, b| a * b);
+/// let parsed = cfgenius::cond_expr!(any(cfg(unix), cfg(not(unix))) ? "4".parse::<u32>() : Ok(0),);
+/// #
+/// # assert!(ARROW == QUESTION);
+/// # assert_eq!(PATHS, u32::MAX);
+/// # assert_eq!(add(2, 3), 5);
+/// # assert_eq!(parsed, Ok(4));
+/// ```
+///
+/// Or, if you want to produce the active value of a cfg key as a string, the `cfg_value` form of
+/// [`cond_str!`](crate::cond_str) can be used instead:
+///
+/// ```plain_text
+/// cond_expr!(cfg_value(<key> in [<value 1>, <value 2>, ...] => <fallback expression>))
+/// ```
+///
+/// Or, if you just want to evaluate a boolean literal for the predicate, the following alias can
+/// be used instead:
+///
+/// ```plain_text
+/// cond_expr!(<predicate>)
+/// ```
+///
+/// Any of these forms can be preceded by a type annotation, which the value of every branch is
+/// coerced to:
+///
+/// ```plain_text
+/// cond_expr!(: <type>, <any of the forms above>)
+/// ```
+///
+/// A branch producing a value of the wrong type is then reported on the branch itself, against the
+/// declared type, rather than wherever the value ends up being used:
+///
+/// ```compile_fail
+/// fn greeting() -> String {
+///     let greeting = cfgenius::cond_expr!(: &str, if true() => String::from("hello") else "hi");
+///     greeting.to_uppercase()
+/// }
+/// ```
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// const LANES: usize = cfgenius::cond_expr!(if cfg(target_pointer_width = "64") => 8 else 4);
+/// const ENDIAN: &str = cfgenius::cond_expr!(cfg(target_endian = "big") => "big", "little");
+/// const WORD: &str = cfgenius::cond_expr!(ptr_width_eq(64) ? "qword" : "dword");
+/// const CACHE_LINE: usize = cfgenius::cond_expr! {
+///     if cfg(target_arch = "aarch64") {
+///         128
+///     } else {
+///         64
+///     }
+/// };
+///
+/// fn describe() -> &'static str {
+///     cfgenius::cond_expr! {
+///         if cfg(unix) {
+///             "unix"
+///         } else if cfg(windows) {
+///             "windows"
+///         } else {
+///             "unknown"
+///         }
+///     }
+/// }
+/// #
+/// # assert!(LANES == 8 || LANES == 4);
+/// # assert_eq!(ENDIAN == "big", cfg!(target_endian = "big"));
+/// # assert_eq!(WORD == "qword", cfg!(target_pointer_width = "64"));
+/// # assert!(CACHE_LINE == 128 || CACHE_LINE == 64);
+/// # let _ = describe();
+/// ```
+///
+/// Since the result is a regular expression, a function can be selected by its path and called
+/// right away. The functions of the different branches don't need to have the same type:
+///
+/// ```
+/// mod simd {
+///     pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+///         a.iter().zip(b).map(|(a, b)| a * b).sum()
+///     }
+/// }
+///
+/// mod scalar {
+///     pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+///         let mut sum = 0.0;
+///         for i in 0..a.len().min(b.len()) {
+///             sum += a[i] * b[i];
+///         }
+///         sum
+///     }
+/// }
+///
+/// let dot = cfgenius::cond_expr!(if feature("simd") => simd::dot else scalar::dot)(
+///     &[1.0, 2.0],
+///     &[3.0, 4.0],
+/// );
+/// #
+/// # assert_eq!(dot, 11.0);
+/// ```
+///
+/// The selected path is a value, though, so it can't be used in a `use` declaration, a type, or a
+/// pattern. Put the `use` declaration in the branches of a [`cond!`](crate::cond) instead, or use
+/// [`cond_ty!`](crate::cond_ty) for types and [`cond_tokens!`](crate::cond_tokens) for patterns.
+///
+/// The expansion does not introduce any labels of its own so invocations can be nested freely and
+/// branches can `break` or `continue` to labels of the surrounding code:
+///
+/// ```
+/// fn first_even(values: &[u32]) -> Option<u32> {
+///     'search: {
+///         for &value in values {
+///             cfgenius::cond_expr! {
+///                 if true() {
+///                     cfgenius::cond_expr! {
+///                         if cfg(all()) {
+///                             if value % 2 == 0 {
+///                                 break 'search Some(value);
+///                             }
+///                         } else {
+///                             continue
+///                         }
+///                     }
+///                 } else {
+///                     ()
+///                 }
+///             };
+///         }
+///
+///         None
+///     }
+/// }
+///
+/// const NESTED: u32 = cfgenius::cond_expr!(
+///     if true() => cfgenius::cond_expr!(if false() => 1 else 2) else 3
+/// );
+/// #
+/// # assert_eq!(first_even(&[1, 3, 4, 5]), Some(4));
+/// # assert_eq!(NESTED, 2);
+/// ```
+///
+/// Likewise, `return` and `?` behave exactly as if they were written in the enclosing function
+/// directly:
+///
+/// ```
+/// use std::num::ParseIntError;
+///
+/// fn parse_port(text: &str) -> Result<u16, ParseIntError> {
+///     let port = cfgenius::cond_expr! {
+///         if cfg(all()) {
+///             text.trim().parse::<u16>()?
+///         } else {
+///             0
+///         }
+///     };
+///
+///     let port = cfgenius::cond_expr!(if true() => port else return Ok(0));
+///     Ok(cfgenius::cond_expr!(false() => unreachable!(), port.max(1)))
+/// }
+///
+/// fn clamp(value: i32) -> i32 {
+///     if value < 0 {
+///         cfgenius::cond_expr!(if cfg(all()) => return 0 else value);
+///     }
+///
+///     cfgenius::cond_expr!(true() ? value.min(100) : return value)
+/// }
+///
+/// fn checked_sum(values: &[&str]) -> Option<u32> {
+///     let mut sum = 0u32;
+///
+///     for value in values {
+///         sum = cfgenius::cond_expr!(: u32, if true() {
+///             sum.checked_add(value.parse().ok()?)?
+///         } else {
+///             sum
+///         });
+///     }
+///
+///     Some(sum)
+/// }
+/// #
+/// # assert_eq!(parse_port(" 8080 "), Ok(8080));
+/// # assert!(parse_port("port").is_err());
+/// # assert_eq!((clamp(-5), clamp(50), clamp(500)), (0, 50, 100));
+/// # assert_eq!(checked_sum(&["1", "2"]), Some(3));
+/// # assert_eq!(checked_sum(&["1", "x"]), None);
+/// ```
+///
+/// The same goes for `.await` in `async` code. Arms can also be `async` blocks themselves and, since
+/// only the matching arm is emitted, the futures of different arms never need to have the same
+/// type. The code using the future only has to work with the future of each arm on the targets
+/// where that arm is selected:
+///
+/// ```
+/// use std::{
+///     future::Future,
+///     pin::pin,
+///     sync::Arc,
+///     task::{Context, Poll, Wake, Waker},
+/// };
+///
+/// async fn fetch() -> u32 {
+///     20
+/// }
+///
+/// async fn load() -> u32 {
+///     let future = cfgenius::cond_expr! {
+///         if all(cfg(all()), true()) {
+///             async { fetch().await + 1 }
+///         } else {
+///             async move { fetch().await * 2 }
+///         }
+///     };
+///
+///     let extra = cfgenius::cond_expr!(true() => fetch().await, 0);
+///     future.await + extra
+/// }
+///
+/// struct Noop;
+///
+/// impl Wake for Noop {
+///     fn wake(self: Arc<Self>) {}
+/// }
+///
+/// fn block_on<F: Future>(future: F) -> F::Output {
+///     let mut future = pin!(future);
+///     let waker = Waker::from(Arc::new(Noop));
+///     let mut cx = Context::from_waker(&waker);
+///
+///     loop {
+///         if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+///             break output;
+///         }
+///     }
+/// }
+/// #
+/// # assert_eq!(block_on(load()), 41);
+/// ```
+///
+/// Arms can likewise be `unsafe` blocks, in any of the forms above:
+///
+/// ```
+/// cfgenius::define!(is_aligned = true());
+///
+/// let values = [1u32, 2, 3];
+/// let ptr = values.as_ptr();
+///
+/// // SAFETY: `ptr` points to the first of three initialized values.
+/// let first = cfgenius::cond_expr!(macro(is_aligned) => unsafe { ptr.read() }, values[0]);
+/// let second = cfgenius::cond_expr!(if macro(is_aligned) {
+///     unsafe { ptr.add(1).read() }
+/// } else {
+///     unsafe { ptr.add(1).read_unaligned() }
+/// });
+/// let third = cfgenius::cond_expr!(macro(!is_aligned) ? values[2] : unsafe { *ptr.add(2) });
+/// #
+/// # assert_eq!((first, second, third), (1, 2, 3));
+/// ```
+///
+/// Since only the matching branch is emitted, branches which diverge, e.g. by panicking, can be
+/// mixed freely with branches producing a value:
+///
+/// ```
+/// cfgenius::define!(is_supported = any(cfg(unix), cfg(windows), true()));
+///
+/// fn page_size() -> usize {
+///     cfgenius::cond_expr! {
+///         if macro(is_supported) {
+///             4096
+///         } else {
+///             unimplemented!("page size of this target")
+///         }
+///     }
+/// }
+///
+/// let halved = cfgenius::cond_expr!(if true() => page_size() / 2 else panic!("unsupported"));
+/// let doubled = cfgenius::cond_expr!(not(true()) => unreachable!(), page_size() * 2);
+/// #
+/// # assert_eq!((halved, doubled), (2048, 8192));
+/// ```
+///
+/// Type annotations also allow each branch to rely on coercions:
+///
+/// ```
+/// use std::fmt::Display;
+///
+/// fn label() -> Box<dyn Display> {
+///     cfgenius::cond_expr!(: Box<dyn Display>, if cfg(unix) {
+///         Box::new("unix")
+///     } else {
+///         Box::new(0)
+///     })
+/// }
+///
+/// const DIVERGING: u32 = cfgenius::cond_expr!(: u32, if true() => 1 else panic!());
+/// #
+/// # assert_eq!(label().to_string() == "unix", cfg!(unix));
+/// # assert_eq!(DIVERGING, 1);
+/// ```
+///
+/// Array and tuple literals are single token trees, so they can be used as arms directly, even if
+/// their lengths or element types differ between arms. Since the result is a constant expression,
+/// it can initialize `const` and `static` arrays and be used as an array length:
+///
+/// ```
+/// cfgenius::define!(has_avx = cfg(target_feature = "avx"));
+///
+/// const LANES: usize = cfgenius::cond_expr!(if macro(has_avx) => 32 else 16);
+/// const ZEROS: [u8; LANES] = cfgenius::cond_expr!(if macro(has_avx) => [0u8; 32] else [0u8; 16]);
+/// static MASKS: [u16; 4] = cfgenius::cond_expr!(if cfg(target_endian = "big") {
+///     [0xff00, 0x00ff, 0xf0f0, 0x0f0f]
+/// } else {
+///     [0x00ff, 0xff00, 0x0f0f, 0xf0f0]
+/// });
+///
+/// type Scratch = [u8; cfgenius::cond_expr!(if ptr_width_at_least(64) => 8 else 4)];
+///
+/// let pair = cfgenius::cond_expr!(cfg(unix) => (1u8, "unix"), (2u8, "other"));
+/// #
+/// # assert_eq!(ZEROS.len(), LANES);
+/// # assert_eq!(MASKS.len(), 4);
+/// # assert_eq!(std::mem::size_of::<Scratch>(), if cfg!(target_pointer_width = "64") { 8 } else { 4 });
+/// # assert_eq!(pair.0 == 1, cfg!(unix));
+/// ```
+///
+/// For the same reason, the result can be used as a const generic argument. Like any other
+/// expression which isn't a literal or a path, the invocation must be wrapped in braces since a
+/// bare macro invocation in generic argument position is parsed as a type:
+///
+/// ```
+/// cfgenius::define!(has_avx = cfg(target_feature = "avx"));
+///
+/// struct Buffer<const N: usize>([u8; N]);
+///
+/// type Lanes = Buffer<{ cfgenius::cond_expr!(if macro(has_avx) { 32 } else { 16 }) }>;
+///
+/// fn capacity<const N: usize>() -> usize {
+///     N
+/// }
+///
+/// let lanes: Lanes = Buffer([0; cfgenius::cond_expr!(macro(has_avx) => 32, 16)]);
+/// let capacity = capacity::<{ cfgenius::cond_expr!(: usize, if cfg(unix) => 8 else 4) }>();
+/// #
+/// # assert_eq!(lanes.0.len(), if cfg!(target_feature = "avx") { 32 } else { 16 });
+/// # assert_eq!(capacity == 8, cfg!(unix));
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_expr {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {};
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) => $($yes:tt)+)
+        else + $(else $($no:tt)+)?
+    ) => {};
+    ($($pred:ident)? ($($pred_args:tt)*) => $yes:expr, $no:expr $(,)?) => {};
+    ($($pred:ident)? ($($pred_args:tt)*) ? $($yes:tt)+ : $no:expr) => {};
+    (cfg_value($key:ident in [$($value:literal),+ $(,)?] => $fallback:expr $(,)?)) => {};
+    ($($pred:ident)? ($($pred_args:tt)*)) => {};
+    (: $ty:ty, $($form:tt)*) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_expr {
+    // The annotation is applied to the result as a whole since only the matching branch is ever
+    // emitted. The lint is allowed since diverging branches would otherwise make the call
+    // unreachable.
+    (: $ty:ty, $($form:tt)*) => {{
+        #[allow(unreachable_code)]
+        $crate::__private::ascribe::<$ty>($crate::cond_expr!($($form)*))
+    }};
+    // `cond!` places the body of the matching branch in the tail position of the enclosing block
+    // so every form can be lowered to a plain block expression, which remains usable in `const`
+    // contexts and const generic arguments.
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else +
+    ) => {
+        $crate::cond_expr! {
+            $(if $($pred)? ($($pred_args)*) {
+                $($yes)*
+            }) else + else {
+                $crate::cond_expr!(@__internal_no_match)
+            }
+        }
+    };
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {{
+        $crate::cond! {
+            $(if $($pred)? ($($pred_args)*) {
+                ({ $($yes)* })
+            }) else + $(else {
+                ({ $($no)* })
+            })?
+        }
+    }};
+    (
+        if $($pred:ident)? ($($pred_args:tt)*) => $($rest:tt)*
+    ) => {
+        $crate::cond_expr! {
+            @__internal_arm_munch [if $($pred)? ($($pred_args)*)] []
+            $($rest)*
+        }
+    };
+    ($($pred:ident)? ($($pred_args:tt)*) => $yes:expr, $no:expr $(,)?) => {{
+        $crate::cond! {
+            if $($pred)? ($($pred_args)*) {
+                $yes
+            } else {
+                $no
+            }
+        }
+    }};
+    ($($pred:ident)? ($($pred_args:tt)*) ? $($rest:tt)*) => {
+        $crate::cond_expr! {
+            @__internal_ternary_munch [$($pred)? ($($pred_args)*)] []
+            $($rest)*
+        }
+    };
+    (cfg_value($($args:tt)*)) => {
+        $crate::cond_str!(cfg_value($($args)*))
+    };
+    ($($pred:ident)? ($($pred_args:tt)*)) => {
+        $crate::cond_expr! {
+            if $($pred)?($($pred_args)*) {
+                true
+            } else {
+                false
+            }
+        }
+    };
+
+    // Expressions cannot be followed by `else` in a macro matcher so we have to find the end of
+    // each expression ourselves by munching through it one token tree at a time.
+    (
+        @__internal_arm_munch [$($chain:tt)*] [$($expr:tt)*]
+        else if $($pred:ident)? ($($pred_args:tt)*) => $($rest:tt)*
+    ) => {
+        $crate::cond_expr! {
+            @__internal_arm_munch [
+                $($chain)* {
+                    ($($expr)*)
+                } else if $($pred)? ($($pred_args)*)
+            ] []
+            $($rest)*
+        }
+    };
+    (
+        @__internal_arm_munch [$($chain:tt)*] [$($expr:tt)*]
+        else $($rest:tt)*
+    ) => {{
+        $crate::cond! {
+            $($chain)* {
+                ($($expr)*)
+            } else {
+                ($($rest)*)
+            }
+        }
+    }};
+    (
+        @__internal_arm_munch [$($chain:tt)*] [$($expr:tt)*]
+        $next:tt $($rest:tt)*
+    ) => {
+        $crate::cond_expr! {
+            @__internal_arm_munch [$($chain)*] [$($expr)* $next]
+            $($rest)*
+        }
+    };
+    (
+        @__internal_arm_munch [$($chain:tt)*] [$($expr:tt)*]
+    ) => {{
+        $crate::cond! {
+            $($chain)* {
+                ($($expr)*)
+            } else {
+                $crate::cond_expr!(@__internal_no_match)
+            }
+        }
+    }};
+    // Likewise, expressions cannot be followed by `:` so the truthy expression of the `?:` ternary
+    // is found by munching up to the first `:` which isn't nested in a group. `::` is a single
+    // token so paths do not end the expression early.
+    (@__internal_ternary_munch [$($pred:tt)*] [$($yes:tt)+] : $no:expr $(,)?) => {
+        $crate::cond_expr!($($pred)* => ($($yes)+), $no)
+    };
+    (@__internal_ternary_munch [$($pred:tt)*] [$($yes:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::cond_expr! {
+            @__internal_ternary_munch [$($pred)*] [$($yes)* $next]
+            $($rest)*
+        }
+    };
+    (@__internal_ternary_munch [$($pred:tt)*] [$($yes:tt)*]) => {
+        ::core::compile_error!("expected `:` followed by the falsy expression of the ternary")
+    };
+    (@__internal_no_match) => {
+        ::core::compile_error!(
+            "`cond_expr!` requires an `else` branch or a matching predicate; none matched on this \
+             target"
+        )
+    };
+}
+
+/// A conditionally-compiled expression whose final `else` branch is required.
+///
+/// ## Syntax
+///
+/// This accepts the same forms as [`cond_expr!`](crate::cond_expr):
+///
+/// ```plain_text
+/// cond_expr_total! {
+///     if <if predicate> {
+///         // arbitrary tokens forming a `BlockExpression`.
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // arbitrary tokens forming a `BlockExpression`.
+///     } else {                         // This is required.
+///         // arbitrary tokens forming a `BlockExpression`.
+///     }
+/// }
+/// ```
+///
+/// ```plain_text
+/// cond_expr_total!(
+///     if <if predicate> => <expression>
+///     else if <else-if predicate> => <expression>  // There can be zero or more of these.
+///     else <expression>                            // This is required.
+/// )
+/// ```
+///
+/// With `cond_expr!`, omitting the final `else` branch is only an error on targets where none of
+/// the predicates match. With this macro, it is always an error, regardless of the target being
+/// compiled for. The ternary, `cfg_value`, and boolean forms of `cond_expr!` always produce a value
+/// and are accepted as-is.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// const LANES: usize = cfgenius::cond_expr_total!(if cfg(target_pointer_width = "64") => 8 else 4);
+/// const CACHE_LINE: usize = cfgenius::cond_expr_total! {
+///     if cfg(target_arch = "aarch64") {
+///         128
+///     } else {
+///         64
+///     }
+/// };
+/// #
+/// # assert!(LANES == 8 || LANES == 4);
+/// # assert!(CACHE_LINE == 128 || CACHE_LINE == 64);
+/// ```
+///
+/// ```compile_fail
+/// const VALUE: u32 = cfgenius::cond_expr_total!(if true() => 1);
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_expr_total {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + else {
+            $($no:tt)*
+        }
+    ) => {};
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) => $($yes:tt)+)
+        else + else $($no:tt)+
+    ) => {};
+    ($($pred:ident)? ($($pred_args:tt)*) => $yes:expr, $no:expr $(,)?) => {};
+    ($($pred:ident)? ($($pred_args:tt)*) ? $($yes:tt)+ : $no:expr) => {};
+    (cfg_value($key:ident in [$($value:literal),+ $(,)?] => $fallback:expr $(,)?)) => {};
+    ($($pred:ident)? ($($pred_args:tt)*)) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_expr_total {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::cond_expr! {
+            $(if $($pred)? ($($pred_args)*) {
+                $($yes)*
+            }) else + else {
+                $($no)*
+            }
+        }
+    };
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else +
+    ) => {
+        $crate::cond_expr_total!(@__internal_missing_else)
+    };
+    (
+        if $($pred:ident)? ($($pred_args:tt)*) => $($rest:tt)*
+    ) => {
+        $crate::cond_expr_total! {
+            @__internal_arm_scan [if $($pred)? ($($pred_args)*) => $($rest)*]
+            $($rest)*
+        }
+    };
+
+    // Like in `cond_expr!`, we have to look for the final `else` one token tree at a time. Unlike
+    // `else if`, a lone `else` followed by an expression can only introduce the final branch.
+    (@__internal_arm_scan [$($tokens:tt)*] else if $($rest:tt)*) => {
+        $crate::cond_expr_total! { @__internal_arm_scan [$($tokens)*] $($rest)* }
+    };
+    (@__internal_arm_scan [$($tokens:tt)*] else $($rest:tt)+) => {
+        $crate::cond_expr! { $($tokens)* }
+    };
+    (@__internal_arm_scan [$($tokens:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::cond_expr_total! { @__internal_arm_scan [$($tokens)*] $($rest)* }
+    };
+    (@__internal_arm_scan [$($tokens:tt)*]) => {
+        $crate::cond_expr_total!(@__internal_missing_else)
+    };
+    (@__internal_missing_else) => {
+        ::core::compile_error!("`cond_expr_total!` requires a final `else` branch")
+    };
+
+    // The remaining forms always produce a value.
+    ($($tokens:tt)*) => {
+        $crate::cond_expr! { $($tokens)* }
+    };
+}
+
+/// Selects the value associated with the first truthy predicate of a list.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// select_first! {
+///     <predicate 1> => <expression 1>,
+///     <predicate 2> => <expression 2>,
+///     // ...
+///     _ => <fallback expression>,  // This is optional.
+/// }
+/// ```
+///
+/// This is a lighter spelling of an `if ... else if ...` chain in [`cond_expr!`](crate::cond_expr),
+/// to which it lowers. Predicates are tried in order and only the expression of the first truthy
+/// one is emitted, which makes this macro well suited to selecting between backends by priority.
+/// Like with `cond_expr!`, omitting the fallback makes it a compile error for none of the
+/// predicates to match on the target being compiled for.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define! {
+///     has_io_uring = all(cfg(target_os = "linux"), false());
+///     has_epoll = true();
+/// }
+///
+/// const BACKEND: &str = cfgenius::select_first! {
+///     macro(has_io_uring) => "io_uring",
+///     macro(has_epoll) => "epoll",
+///     _ => "poll",
+/// };
+///
+/// fn page_size() -> usize {
+///     cfgenius::select_first! {
+///         cfg(target_arch = "aarch64") => 16 * 1024,
+///         cfg(all()) => 4 * 1024,
+///     }
+/// }
+/// #
+/// # assert_eq!(BACKEND, "epoll");
+/// # assert!(page_size() >= 4096);
+/// ```
+///
+/// ```compile_fail
+/// const VALUE: u32 = cfgenius::select_first!(false() => 1, cfg(any()) => 2);
+/// ```
+#[macro_export]
+macro_rules! select_first {
+    (
+        $($($pred:ident)? ($($pred_args:tt)*) => $value:expr),+
+        $(, _ => $fallback:expr)? $(,)?
+    ) => {
+        $crate::cond_expr! {
+            $(if $($pred)? ($($pred_args)*) {
+                $value
+            }) else + $(else {
+                $fallback
+            })?
+        }
+    };
+}
+
+/// An attribute which only includes the item to which it is applied if the provided predicate is
+/// truthy.
+///
+/// This is the attribute counterpart of [`cond!`](crate::cond) and is only available with the
+/// `attr` feature. It cannot be named `cond` because attribute macros and function-like macros
+/// share a namespace.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// #[cfgenius::when(<predicate>)]
+/// <item>
+/// ```
+///
+/// The attribute expands to an invocation of `cfgenius::cond!` so `cfgenius` must be accessible
+/// under that name.
+///
+/// The predicate can optionally be followed by the `doc_cfg` option:
+///
+/// ```plain_text
+/// #[cfgenius::when(<predicate>, doc_cfg)]
+/// <item>
+/// ```
+///
+/// This additionally annotates the item with `#[cfg_attr(docsrs, doc(cfg(...)))]` so that its
+/// documentation on docs.rs notes the configurations in which it is available. The annotation is
+/// only emitted if the predicate can be expressed as a regular cfg predicate, which is not the case
+/// for custom variables or the counting predicates. Crates using this option must enable the
+/// unstable `doc_cfg` feature when documented with the `docsrs` cfg, e.g. by adding
+/// `#![cfg_attr(docsrs, feature(doc_cfg))]` to their crate root.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define!(is_supported = any(cfg(unix), cfg(windows)));
+///
+/// #[cfgenius::when(all(macro(is_supported), not(cfg(miri))))]
+/// fn fast_path() {}
+///
+/// #[cfgenius::when(not(all(macro(is_supported), not(cfg(miri)))))]
+/// fn fast_path() {}
+///
+/// #[cfgenius::when(any(cfg(unix), target_os("wasi")), doc_cfg)]
+/// pub fn open_by_path() {}
+/// #
+/// # fast_path();
+/// ```
+#[cfg(feature = "attr")]
+pub use cfgenius_macros::when;
+
+/// A conditionally-compiled set of attributes applied to an item.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_attr! {
+///     if <if predicate> {
+///         // zero or more attributes
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // zero or more attributes
+///     } else {                         // This is optional.
+///         // zero or more attributes
+///     }
+///
+///     // the item to which the attributes are applied
+/// }
+/// ```
+///
+/// The item is always emitted. If no branch matches, it is emitted without any additional
+/// attributes.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::cond_attr! {
+///     if cfg(target_pointer_width = "64") {
+///         #[repr(C, align(8))]
+///     } else {
+///         #[repr(C)]
+///     }
+///
+///     #[derive(Debug, Copy, Clone)]
+///     pub struct Header {
+///         pub len: u32,
+///         pub flags: u32,
+///     }
+/// }
+/// #
+/// # let _ = Header { len: 0, flags: 0 };
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_attr {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $(#[$yes:meta])*
+        }) else + $(else {
+            $(#[$no:meta])*
+        })?
+
+        $item:item
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_attr {
+    // The item follows the chain without any separator, so we can't match the chain and the item in
+    // a single rule without running into parsing ambiguities. Instead, we split them apart by
+    // munching through the chain one branch at a time.
+    (if $($tokens:tt)*) => {
+        $crate::cond_attr! { @__internal_split [] if $($tokens)* }
+    };
+
+    (
+        @__internal_split [$($chain:tt)*]
+        if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        } else $($rest:tt)*
+    ) => {
+        $crate::cond_attr! {
+            @__internal_split [
+                $($chain)*
+                if $($pred)? ($($pred_args)*) {
+                    $($yes)*
+                } else
+            ]
+            $($rest)*
+        }
+    };
+    (
+        @__internal_split [$($chain:tt)*]
+        if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }
+        $($item:tt)*
+    ) => {
+        $crate::cond_attr! {
+            @__internal_chained_munch { $($item)* }
+            $($chain)*
+            if $($pred)? ($($pred_args)*) {
+                $($yes)*
+            }
+        }
+    };
+    (
+        @__internal_split [$($chain:tt)*]
+        {
+            $($no:tt)*
+        }
+        $($item:tt)*
+    ) => {
+        $crate::cond_attr! {
+            @__internal_chained_munch { $($item)* }
+            $($chain)*
+            {
+                $($no)*
+            }
+        }
+    };
+
+    // Now, we can emit the item alongside the attributes of the first matching branch.
+    (
+        @__internal_chained_munch { $($item:tt)* }
+        if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        } $(else $($rest:tt)*)?
+    ) => {
+        $crate::cond! {
+            if $($pred)? ($($pred_args)*) {
+                $($yes)*
+                $($item)*
+            } else {
+                $crate::cond_attr! {
+                    @__internal_chained_munch { $($item)* }
+                    $($($rest)*)?
+                }
+            }
+        }
+    };
+    (
+        @__internal_chained_munch { $($item:tt)* }
+        { $($attrs:tt)* }
+    ) => {
+        $($attrs)*
+        $($item)*
+    };
+    (
+        @__internal_chained_munch { $($item:tt)* }
+    ) => {
+        $($item)*
+    };
+}
+
+/// An enum whose variants can be conditionally compiled.
+///
+/// Macros cannot be invoked in place of an enum variant, so `cond!` cannot be used inside an enum
+/// body directly. Instead, the enum can be wrapped in this macro, which allows `cond!` to be
+/// written among its variants.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_variants! {
+///     <attributes> <visibility> enum <name> <generics> <where clause> {
+///         // zero or more variants
+///
+///         cond! {
+///             if <if predicate> {
+///                 // zero or more variants
+///             } else if <else-if predicate> {  // There can be zero or more of these.
+///                 // zero or more variants
+///             } else {                         // This is optional.
+///                 // zero or more variants
+///             }
+///         },
+///
+///         // zero or more variants, interleaved with further `cond!` invocations
+///     }
+/// }
+/// ```
+///
+/// Variants, including those in branches, are separated by commas like they would be in a
+/// regular enum, and branches can contain `cond!` invocations of their own. Only the variants of
+/// the first matching branch of each `cond!` are included in the enum.
+///
+/// Variants gated by `cfg` predicates alone can simply be annotated with a regular `#[cfg(...)]`
+/// attribute. This macro is mostly useful for variants which depend on custom variables.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define!(has_sockets = any(cfg(unix), cfg(windows)));
+///
+/// cfgenius::cond_variants! {
+///     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///     pub enum Transport<'a> {
+///         Memory(&'a [u8]),
+///         cond! {
+///             if macro(has_sockets) {
+///                 Tcp { port: u16 },
+///                 Udp { port: u16 },
+///             } else {
+///                 Disabled
+///             }
+///         },
+///         cond! {
+///             if all(macro(has_sockets), cfg(unix)) {
+///                 Unix(&'a str),
+///             }
+///         }
+///         #[allow(dead_code)]
+///         Other,
+///     }
+/// }
+///
+/// fn describe(transport: Transport<'_>) -> &'static str {
+///     match transport {
+///         Transport::Memory(_) => "memory",
+///         cfgenius::cond_tokens!(if macro(has_sockets) {
+///             Transport::Tcp { .. } | Transport::Udp { .. }
+///         } else {
+///             Transport::Disabled
+///         }) => "network",
+///         _ => "other",
+///     }
+/// }
+/// #
+/// # assert_eq!(describe(Transport::Memory(&[])), "memory");
+/// #
+/// # cfgenius::cond_variants! {
+/// #     #[allow(dead_code)]
+/// #     enum Many<T> where T: Copy {
+/// #         V0, V1, V2, V3, V4, V5, V6, V7,
+/// #         V8, V9, V10, V11, V12, V13, V14, V15,
+/// #         V16, V17, V18, V19, V20, V21, V22, V23,
+/// #         V24, V25, V26, V27, V28, V29, V30, V31,
+/// #         V32, V33, V34, V35, V36, V37, V38, V39,
+/// #         V40, V41, V42, V43, V44, V45, V46, V47,
+/// #         V48, V49, V50, V51, V52, V53, V54, V55,
+/// #         V56, V57, V58, V59, V60, V61, V62, V63,
+/// #         V64, V65, V66, V67, V68, V69, V70, V71,
+/// #         V72, V73, V74, V75, V76, V77, V78, V79,
+/// #         V80, V81, V82, V83, V84, V85, V86, V87,
+/// #         V88, V89, V90, V91, V92, V93, V94, V95,
+/// #         V96, V97, V98, V99, V100, V101, V102, V103,
+/// #         V104, V105, V106, V107, V108, V109, V110, V111,
+/// #         V112, V113, V114, V115, V116, V117, V118, V119,
+/// #         V120, V121, V122, V123, V124, V125, V126, V127,
+/// #         V128, V129, V130, V131, V132, V133, V134, V135,
+/// #         cond! { if macro(has_sockets) { Socket(T), } }
+/// #         Last,
+/// #     }
+/// # }
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_variants {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident $($generics_and_where_clause:tt)*
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_variants {
+    // Generics and `where` clauses make the header of the enum hard to match, so we move tokens
+    // into it until only the body is left. Since the body is the last token, any tokens followed by
+    // at least one more belong to the header and are moved eight at a time.
+    (@__internal_header [$($header:tt)*] { $($body:tt)* }) => {
+        $crate::cond_variants! { @__internal_munch [$($header)*] [] $($body)* }
+    };
+    (
+        @__internal_header [$($header:tt)*]
+        $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $next:tt $($rest:tt)*
+    ) => {
+        $crate::cond_variants! {
+            @__internal_header [$($header)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $next $($rest)*
+        }
+    };
+    (@__internal_header [$($header:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::cond_variants! { @__internal_header [$($header)* $next] $($rest)* }
+    };
+    (@__internal_header [$($header:tt)*]) => {
+        ::core::compile_error!("expected an enum with a body");
+    };
+
+    // Each variant is collected as a group so commas, including those left over after splicing in
+    // the variants of a branch, can be skipped freely.
+    (@__internal_munch $header:tt [$($done:tt)*] , $($rest:tt)*) => {
+        $crate::cond_variants! { @__internal_munch $header [$($done)*] $($rest)* }
+    };
+    (@__internal_munch $header:tt $done:tt cond! { $($chain:tt)* } $($rest:tt)*) => {
+        $crate::cond_variants! { @__internal_branch $header $done [$($rest)*] $($chain)* }
+    };
+    // `cond!` cannot be told apart from a variant without matching the variants before it one by
+    // one, so runs of plain variants are matched eight at a time to save on the recursion limit.
+    (
+        @__internal_munch $header:tt [$($done:tt)*]
+        $(#[$a0:meta])* $v0:ident $(($($t0:tt)*))? $({ $($f0:tt)* })? $(= $d0:expr)?,
+        $(#[$a1:meta])* $v1:ident $(($($t1:tt)*))? $({ $($f1:tt)* })? $(= $d1:expr)?,
+        $(#[$a2:meta])* $v2:ident $(($($t2:tt)*))? $({ $($f2:tt)* })? $(= $d2:expr)?,
+        $(#[$a3:meta])* $v3:ident $(($($t3:tt)*))? $({ $($f3:tt)* })? $(= $d3:expr)?,
+        $(#[$a4:meta])* $v4:ident $(($($t4:tt)*))? $({ $($f4:tt)* })? $(= $d4:expr)?,
+        $(#[$a5:meta])* $v5:ident $(($($t5:tt)*))? $({ $($f5:tt)* })? $(= $d5:expr)?,
+        $(#[$a6:meta])* $v6:ident $(($($t6:tt)*))? $({ $($f6:tt)* })? $(= $d6:expr)?,
+        $(#[$a7:meta])* $v7:ident $(($($t7:tt)*))? $({ $($f7:tt)* })? $(= $d7:expr)?,
+        $($rest:tt)*
+    ) => {
+        $crate::cond_variants! {
+            @__internal_munch $header [
+                $($done)*
+                { $(#[$a0])* $v0 $(($($t0)*))? $({ $($f0)* })? $(= $d0)? }
+                { $(#[$a1])* $v1 $(($($t1)*))? $({ $($f1)* })? $(= $d1)? }
+                { $(#[$a2])* $v2 $(($($t2)*))? $({ $($f2)* })? $(= $d2)? }
+                { $(#[$a3])* $v3 $(($($t3)*))? $({ $($f3)* })? $(= $d3)? }
+                { $(#[$a4])* $v4 $(($($t4)*))? $({ $($f4)* })? $(= $d4)? }
+                { $(#[$a5])* $v5 $(($($t5)*))? $({ $($f5)* })? $(= $d5)? }
+                { $(#[$a6])* $v6 $(($($t6)*))? $({ $($f6)* })? $(= $d6)? }
+                { $(#[$a7])* $v7 $(($($t7)*))? $({ $($f7)* })? $(= $d7)? }
+            ]
+            $($rest)*
+        }
+    };
+    (
+        @__internal_munch $header:tt [$($done:tt)*]
+        $(#[$attr:meta])*
+        $variant:ident $(($($tuple:tt)*))? $({ $($fields:tt)* })? $(= $discriminant:expr)?
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::cond_variants! {
+            @__internal_munch $header [
+                $($done)*
+                {
+                    $(#[$attr])*
+                    $variant $(($($tuple)*))? $({ $($fields)* })? $(= $discriminant)?
+                }
+            ]
+            $($($rest)*)?
+        }
+    };
+    (@__internal_munch [$($header:tt)*] [$({ $($variant:tt)* })*]) => {
+        $($header)* {
+            $($($variant)*,)*
+        }
+    };
+    (@__internal_munch $header:tt $done:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected an enum variant or `cond! { ... }`, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+
+    // Only the matching branch is ever expanded so the variants of each branch are spliced in
+    // front of the remaining ones and munched like any other variant.
+    (
+        @__internal_branch $header:tt $done:tt $rest:tt
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $crate::cond! {
+            $(if $($pred)? ($($pred_args)*) {
+                $crate::cond_variants! { @__internal_splice $header $done [$($yes)*] $rest }
+            }) else + else {
+                $crate::cond_variants! { @__internal_splice $header $done [$($($no)*)?] $rest }
+            }
+        }
+    };
+    (@__internal_splice $header:tt $done:tt [$($variants:tt)*] [$($rest:tt)*]) => {
+        $crate::cond_variants! { @__internal_munch $header $done $($variants)* , $($rest)* }
+    };
+    (@__internal_branch $header:tt $done:tt $rest:tt $($chain:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a chain of `if <predicate> { ... }` branches, found `",
+            ::core::stringify!($($chain)*),
+            "`",
+        ));
+    };
+
+    ($($tokens:tt)*) => {
+        $crate::cond_variants! { @__internal_header [] $($tokens)* }
+    };
+}
+
+/// A struct whose fields can be conditionally compiled.
+///
+/// This is the struct counterpart of [`cond_variants!`](crate::cond_variants). Like enum variants,
+/// fields can only be gated by a regular `#[cfg(...)]` attribute, which cannot refer to custom
+/// variables. Wrapping the struct in this macro allows `cond!` to be written among its fields.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_fields! {
+///     <attributes> <visibility> struct <name> <generics> <where clause> {
+///         // zero or more fields
+///
+///         cond! {
+///             if <if predicate> {
+///                 // zero or more fields
+///             } else if <else-if predicate> {  // There can be zero or more of these.
+///                 // zero or more fields
+///             } else {                         // This is optional.
+///                 // zero or more fields
+///             }
+///         },
+///
+///         // zero or more fields, interleaved with further `cond!` invocations
+///     }
+/// }
+/// ```
+///
+/// Each field keeps its attributes and visibility. Fields, including those in branches, are
+/// separated by commas like they would be in a regular struct, and branches can contain `cond!`
+/// invocations of their own. Only the fields of the first matching branch of each `cond!` are
+/// included in the struct. Only structs with named fields are supported.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define!(is_tracked = any(cfg(debug_assertions), false()));
+///
+/// cfgenius::cond_fields! {
+///     #[derive(Debug, Default)]
+///     pub struct Buffer<T> {
+///         pub items: Vec<T>,
+///         cond! {
+///             if macro(is_tracked) {
+///                 /// The number of times the buffer has been grown.
+///                 pub(crate) grown: usize,
+///                 #[allow(dead_code)]
+///                 label: &'static str,
+///             }
+///         }
+///     }
+/// }
+///
+/// impl<T> Buffer<T> {
+///     pub fn push(&mut self, item: T) {
+///         cfgenius::cond! {
+///             if macro(is_tracked) {
+///                 if self.items.len() == self.items.capacity() {
+///                     self.grown += 1;
+///                 }
+///             }
+///         }
+///
+///         self.items.push(item);
+///     }
+/// }
+/// #
+/// # let mut buffer = Buffer::default();
+/// # buffer.push(1);
+/// # assert_eq!(buffer.items, [1]);
+/// #
+/// # cfgenius::cond_fields! {
+/// #     #[derive(Default)]
+/// #     struct Many<T> where T: Copy {
+/// #         f0: u8, f1: u8, f2: u8, f3: u8, f4: u8, f5: u8, f6: u8, f7: u8,
+/// #         f8: u8, f9: u8, f10: u8, f11: u8, f12: u8, f13: u8, f14: u8, f15: u8,
+/// #         f16: u8, f17: u8, f18: u8, f19: u8, f20: u8, f21: u8, f22: u8, f23: u8,
+/// #         f24: u8, f25: u8, f26: u8, f27: u8, f28: u8, f29: u8, f30: u8, f31: u8,
+/// #         f32: u8, f33: u8, f34: u8, f35: u8, f36: u8, f37: u8, f38: u8, f39: u8,
+/// #         f40: u8, f41: u8, f42: u8, f43: u8, f44: u8, f45: u8, f46: u8, f47: u8,
+/// #         f48: u8, f49: u8, f50: u8, f51: u8, f52: u8, f53: u8, f54: u8, f55: u8,
+/// #         f56: u8, f57: u8, f58: u8, f59: u8, f60: u8, f61: u8, f62: u8, f63: u8,
+/// #         f64: u8, f65: u8, f66: u8, f67: u8, f68: u8, f69: u8, f70: u8, f71: u8,
+/// #         f72: u8, f73: u8, f74: u8, f75: u8, f76: u8, f77: u8, f78: u8, f79: u8,
+/// #         f80: u8, f81: u8, f82: u8, f83: u8, f84: u8, f85: u8, f86: u8, f87: u8,
+/// #         f88: u8, f89: u8, f90: u8, f91: u8, f92: u8, f93: u8, f94: u8, f95: u8,
+/// #         f96: u8, f97: u8, f98: u8, f99: u8, f100: u8, f101: u8, f102: u8, f103: u8,
+/// #         f104: u8, f105: u8, f106: u8, f107: u8, f108: u8, f109: u8, f110: u8, f111: u8,
+/// #         f112: u8, f113: u8, f114: u8, f115: u8, f116: u8, f117: u8, f118: u8, f119: u8,
+/// #         f120: u8, f121: u8, f122: u8, f123: u8, f124: u8, f125: u8, f126: u8, f127: u8,
+/// #         f128: u8, f129: u8, f130: u8, f131: u8, f132: u8, f133: u8, f134: u8, f135: u8,
+/// #         cond! { if macro(is_tracked) { extra: T, } }
+/// #         last: u8
+/// #     }
+/// # }
+/// #
+/// # let _ = Many::<u8>::default().f135;
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_fields {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident $($generics_and_where_clause:tt)*
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_fields {
+    // See `cond_variants!` for how the struct is assembled.
+    (@__internal_header [$($header:tt)*] { $($body:tt)* }) => {
+        $crate::cond_fields! { @__internal_munch [$($header)*] [] $($body)* }
+    };
+    (
+        @__internal_header [$($header:tt)*]
+        $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $next:tt $($rest:tt)*
+    ) => {
+        $crate::cond_fields! {
+            @__internal_header [$($header)* $t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7] $next $($rest)*
+        }
+    };
+    (@__internal_header [$($header:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::cond_fields! { @__internal_header [$($header)* $next] $($rest)* }
+    };
+    (@__internal_header [$($header:tt)*]) => {
+        ::core::compile_error!("expected a struct with named fields");
+    };
+
+    (@__internal_munch $header:tt [$($done:tt)*] , $($rest:tt)*) => {
+        $crate::cond_fields! { @__internal_munch $header [$($done)*] $($rest)* }
+    };
+    (@__internal_munch $header:tt $done:tt cond! { $($chain:tt)* } $($rest:tt)*) => {
+        $crate::cond_fields! { @__internal_branch $header $done [$($rest)*] $($chain)* }
+    };
+    (
+        @__internal_munch $header:tt [$($done:tt)*]
+        $(#[$a0:meta])* $vis0:vis $f0:ident : $ty0:ty,
+        $(#[$a1:meta])* $vis1:vis $f1:ident : $ty1:ty,
+        $(#[$a2:meta])* $vis2:vis $f2:ident : $ty2:ty,
+        $(#[$a3:meta])* $vis3:vis $f3:ident : $ty3:ty,
+        $(#[$a4:meta])* $vis4:vis $f4:ident : $ty4:ty,
+        $(#[$a5:meta])* $vis5:vis $f5:ident : $ty5:ty,
+        $(#[$a6:meta])* $vis6:vis $f6:ident : $ty6:ty,
+        $(#[$a7:meta])* $vis7:vis $f7:ident : $ty7:ty,
+        $($rest:tt)*
+    ) => {
+        $crate::cond_fields! {
+            @__internal_munch $header [
+                $($done)*
+                { $(#[$a0])* $vis0 $f0: $ty0 }
+                { $(#[$a1])* $vis1 $f1: $ty1 }
+                { $(#[$a2])* $vis2 $f2: $ty2 }
+                { $(#[$a3])* $vis3 $f3: $ty3 }
+                { $(#[$a4])* $vis4 $f4: $ty4 }
+                { $(#[$a5])* $vis5 $f5: $ty5 }
+                { $(#[$a6])* $vis6 $f6: $ty6 }
+                { $(#[$a7])* $vis7 $f7: $ty7 }
+            ]
+            $($rest)*
+        }
+    };
+    (
+        @__internal_munch $header:tt [$($done:tt)*]
+        $(#[$attr:meta])*
+        $vis:vis $field:ident : $ty:ty
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::cond_fields! {
+            @__internal_munch $header [
+                $($done)*
+                {
+                    $(#[$attr])*
+                    $vis $field: $ty
+                }
+            ]
+            $($($rest)*)?
+        }
+    };
+    (@__internal_munch [$($header:tt)*] [$({ $($field:tt)* })*]) => {
+        $($header)* {
+            $($($field)*,)*
+        }
+    };
+    (@__internal_munch $header:tt $done:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a named field or `cond! { ... }`, found `",
+            ::core::stringify!($($rest)*),
+            "`",
+        ));
+    };
+
+    (
+        @__internal_branch $header:tt $done:tt $rest:tt
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $crate::cond! {
+            $(if $($pred)? ($($pred_args)*) {
+                $crate::cond_fields! { @__internal_splice $header $done [$($yes)*] $rest }
+            }) else + else {
+                $crate::cond_fields! { @__internal_splice $header $done [$($($no)*)?] $rest }
+            }
+        }
+    };
+    (@__internal_splice $header:tt $done:tt [$($fields:tt)*] [$($rest:tt)*]) => {
+        $crate::cond_fields! { @__internal_munch $header $done $($fields)* , $($rest)* }
+    };
+    (@__internal_branch $header:tt $done:tt $rest:tt $($chain:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a chain of `if <predicate> { ... }` branches, found `",
+            ::core::stringify!($($chain)*),
+            "`",
+        ));
+    };
+
+    ($($tokens:tt)*) => {
+        $crate::cond_fields! { @__internal_header [] $($tokens)* }
+    };
+}
+
+/// A conditionally-compiled module declaration.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_mod! {
+///     if <if predicate> {
+///         // a module declaration
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // a module declaration
+///     } else {                         // This is optional.
+///         // a module declaration
+///     }
+/// }
+/// ```
+///
+/// Each branch must contain exactly one module declaration, which can either be inline
+/// (`mod <name> { ... }`) or refer to a file (`mod <name>;`), and may be preceded by attributes
+/// such as `#[path = "..."]`. Only the declaration of the first matching branch is emitted.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```no_compile
+/// cfgenius::cond_mod! {
+///     if all(cfg(unix), macro(is_supported)) {
+///         #[path = "imp/unix.rs"]
+///         mod imp;
+///     } else {
+///         mod imp {
+///             pub fn init() {}
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! cond_mod {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $(#[$yes_attr:meta])*
+            $yes_vis:vis mod $yes_name:ident $yes_body:tt
+        }) else + $(else {
+            $(#[$no_attr:meta])*
+            $no_vis:vis mod $no_name:ident $no_body:tt
+        })?
+    ) => {
+        $crate::cond! {
+            $(if $($pred)? ($($pred_args)*) {
+                $(#[$yes_attr])*
+                $yes_vis mod $yes_name $yes_body
+            }) else + $(else {
+                $(#[$no_attr])*
+                $no_vis mod $no_name $no_body
+            })?
+        }
+    };
+    ($($tokens:tt)*) => {
+        ::core::compile_error!("each branch of `cond_mod!` must contain exactly one module declaration");
+    };
+}
+
+/// A conditionally-compiled type.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_ty! {
+///     if <if predicate> {
+///         // a type
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // a type
+///     } else {                         // This is required.
+///         // a type
+///     }
+/// }
+/// ```
+///
+/// Unlike [`cond!`](crate::cond), the final `else` branch is required since the macro must always
+/// expand to some type.
+///
+/// The selected type is emitted as a projection through an associated type. This makes it usable
+/// in most type positions, including struct fields and type aliases, but not in positions which
+/// reject projections such as the self type of an inherent `impl` block.
+///
+/// ## Every Branch Is Type-Checked
+///
+/// Types cannot carry `#[cfg(...)]` attributes, so the branches which don't match are not removed
+/// like they are by the other macros of this crate. Every branch must name a type which exists on
+/// every target being compiled for, even if its predicate is falsy there:
+///
+/// ```compile_fail,E0433
+/// type Handle = cfgenius::cond_ty!(if cfg(any()) { std::os::nonexistent::Handle } else { u64 });
+/// ```
+///
+/// In particular, this rules out selecting between platform-specific types such as
+/// `std::os::windows::io::RawHandle`. Define a type alias in each branch of a
+/// [`cond!`](crate::cond) instead:
+///
+/// ```
+/// cfgenius::cond! {
+///     if cfg(windows) {
+///         pub type Handle = std::os::windows::io::RawHandle;
+///     } else {
+///         pub type Handle = u64;
+///     }
+/// }
+/// #
+/// # let _: Option<Handle> = None;
+/// ```
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// pub type Word = cfgenius::cond_ty! {
+///     if cfg(target_pointer_width = "64") {
+///         u64
+///     } else {
+///         u32
+///     }
+/// };
+///
+/// pub struct Buffer<T> {
+///     pub items: cfgenius::cond_ty!(if feature("small_vec") { [T; 4] } else { Vec<T> }),
+///     pub len: Word,
+/// }
+/// #
+/// # let _ = Buffer::<u8> { items: Vec::new(), len: 0 };
+/// ```
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_ty {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + else {
+            $($no:tt)*
+        }
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_ty {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + else {
+            $($no:tt)*
+        }
+    ) => {
+        $crate::cond_ty! {
+            @__internal_chained_munch
+            $(
+                if $($pred)? ($($pred_args)*) {
+                    $($yes)*
+                }
+            ) else + else {
+                $($no)*
+            }
+        }
+    };
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else +
+    ) => {
+        ::core::compile_error!("`cond_ty!` requires a final `else` branch")
+    };
+
+    // `cfg` predicates can only be evaluated by attributes, which cannot be applied in type
+    // position. Instead, we evaluate each predicate into a `const` boolean and select the
+    // corresponding type through a trait.
+    (
+        @__internal_chained_munch
+        if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        } else $($rest:tt)*
+    ) => {
+        <$crate::__private::Cond<{ $crate::cond_expr!($($pred)? ($($pred_args)*)) }>
+            as $crate::__private::Select<
+                $($yes)*,
+                $crate::cond_ty! { @__internal_chained_munch $($rest)* },
+            >>::Output
+    };
+    (
+        @__internal_chained_munch
+        { $($no:tt)* }
+    ) => {
+        $($no)*
+    };
+}
+
+/// A conditionally-compiled string.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_str! {
+///     if <if predicate> {
+///         // an expression producing a `&'static str`
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // an expression producing a `&'static str`
+///     } else {                         // This is required.
+///         // an expression producing a `&'static str`
+///     }
+/// }
+/// ```
+///
+/// Like [`cond_ty!`](crate::cond_ty), the final `else` branch is required since the macro must
+/// always produce some string. Each branch is typically a string literal but any expression of
+/// type `&'static str`, such as an invocation of `concat!` or `env!`, can be used.
+///
+/// Or, if you want to produce the active value of a cfg key, the following form can be used
+/// instead:
+///
+/// ```plain_text
+/// cond_str!(cfg_value(<key> in [<value 1>, <value 2>, ...] => <fallback expression>))
+/// ```
+///
+/// Since `rustc` does not expose the value of a cfg key directly, this checks the key against
+/// each of the candidate string literals in order and produces the first one which matches or the
+/// fallback if none of them do. This form can also be used with [`cond_expr!`](crate::cond_expr).
+///
+/// The result is an expression of type `&'static str`, not a string literal token. It can be used
+/// in `const` and `static` items but not as an argument to macros which require a literal, such as
+/// `concat!`, `include_str!`, or `env!`. Move those invocations into the branches instead, as in
+/// the example below.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// const LIB: &str = cfgenius::cond_str!(if cfg(windows) { "foo.dll" } else { "libfoo.so" });
+///
+/// const ARCH: &str = cfgenius::cond_str!(cfg_value(
+///     target_arch in ["x86_64", "aarch64", "riscv64"] => "other"
+/// ));
+///
+/// const TRIPLE: &str = cfgenius::cond_str! {
+///     if cfg(target_endian = "little") {
+///         concat!(env!("CARGO_PKG_NAME"), "-le")
+///     } else {
+///         concat!(env!("CARGO_PKG_NAME"), "-be")
+///     }
+/// };
+/// #
+/// # assert_eq!(LIB == "foo.dll", cfg!(windows));
+/// # assert_eq!(ARCH == "x86_64", cfg!(target_arch = "x86_64"));
+/// # assert!(TRIPLE.ends_with("-le") || TRIPLE.ends_with("-be"));
+/// ```
+#[macro_export]
+macro_rules! cond_str {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $yes:expr
+        }) else + else {
+            $no:expr
+        }
+    ) => {
+        $crate::__private::as_str({
+            $crate::cond! {
+                $(if $($pred)? ($($pred_args)*) {
+                    $yes
+                }) else + else {
+                    $no
+                }
+            }
+        })
+    };
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else +
+    ) => {
+        ::core::compile_error!("`cond_str!` requires a final `else` branch")
+    };
+    (cfg_value($key:ident in [$($value:literal),+ $(,)?] => $fallback:expr $(,)?)) => {
+        $crate::cond_str! {
+            $(if cfg($key = $value) {
+                $value
+            }) else + else {
+                $fallback
+            }
+        }
+    };
+}
+
+/// Conditionally-selected raw tokens.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_tokens! {
+///     if <if predicate> {
+///         // arbitrary tokens
+///     } else if <else-if predicate> {  // There can be zero or more of these.
+///         // arbitrary tokens
+///     } else {                         // This is optional.
+///         // arbitrary tokens
+///     }
+/// }
+/// ```
+///
+/// The tokens of the matching branch are emitted verbatim, without any wrapping block, which makes
+/// this macro usable in positions such as patterns.
+///
+/// Because `cfg` predicates can only be resolved by attributes, which cannot be applied to
+/// arbitrary tokens, the `cfg`, `cfg_not`, `cfg_eq`, `feature`, `target_os`, `rustc_at_least`,
+/// `ptr_width_eq`, `ptr_width_at_least`, and `between` predicates are rejected by this macro, as
+/// are the spliced lists of `all_spliced` and `any_spliced`. Use them to [`define!`](crate::define)
+/// a variable and refer to that variable using `macro(...)` instead.
+///
+/// ```compile_fail
+/// // error: `cond_tokens!` cannot evaluate `between` predicates; ...
+/// type Word = cfgenius::cond_tokens!(
+///     if between(64, 64, target_pointer_width) { u64 } else { u32 }
+/// );
+/// ```
+///
+/// Like with [`cond!`](crate::cond), the predicates of every branch are checked, even those of
+/// branches which are never taken:
+///
+/// ```compile_fail
+/// cfgenius::define!(is_enabled = true());
+///
+/// // error: expected a predicate like `cfg(unix)`, found `unix`
+/// type Word = cfgenius::cond_tokens!(
+///     if macro(is_enabled) { u64 } else if all(macro(is_enabled), unix) { u32 } else { u16 }
+/// );
+/// ```
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define!(is_64_bit = cfg(target_pointer_width = "64"));
+///
+/// fn is_native(bits: u32) -> bool {
+///     match bits {
+///         cfgenius::cond_tokens!(if macro(is_64_bit) { 64 } else { 32 }) => true,
+///         _ => false,
+///     }
+/// }
+/// #
+/// # assert_eq!(is_native(64), cfg!(target_pointer_width = "64"));
+/// ```
+///
+/// Groups of variables are resolved without attributes as well, so `all`, `any`, and `not` can be
+/// used in type and pattern positions too:
+///
+/// ```
+/// cfgenius::define! {
+///     is_64_bit = cfg(target_pointer_width = "64");
+///     is_big_endian = cfg(target_endian = "big");
+/// }
+///
+/// type Word = cfgenius::cond_tokens!(
+///     if all(macro(is_64_bit), not(macro(is_big_endian))) { u64 } else { u32 }
+/// );
+///
+/// fn is_wide(bits: u32) -> bool {
+///     match bits {
+///         cfgenius::cond_tokens!(if any(not(macro(is_64_bit)), all(macro(is_big_endian))) {
+///             32
+///         } else {
+///             64
+///         }) => true,
+///         _ => false,
+///     }
+/// }
+/// #
+/// # let little_64 = cfg!(all(target_pointer_width = "64", target_endian = "little"));
+/// # assert_eq!(core::mem::size_of::<Word>() == 8, little_64);
+/// # assert_eq!(is_wide(64), little_64);
+/// ```
+///
+/// ## Gating `where` Bounds
+///
+/// Macros cannot be invoked in place of a bound but they can be invoked in place of the bounded
+/// type. Hence, a bound can be made conditional without duplicating the rest of the signature by
+/// selecting between the generic parameter and a type which trivially satisfies the bound, such as
+/// `()`:
+///
+/// ```
+/// cfgenius::define!(is_threaded = any(cfg(unix), cfg(windows), true()));
+///
+/// fn spawn_task<T>(task: T) -> T
+/// where
+///     T: Clone,
+///     cfgenius::cond_tokens!(if macro(is_threaded) { T } else { () }): Send,
+/// {
+///     task
+/// }
+/// #
+/// # assert_eq!(spawn_task(1), 1);
+/// ```
+///
+/// ```compile_fail,E0277
+/// cfgenius::define!(is_threaded = true());
+///
+/// fn spawn_task<T>(task: T) -> T
+/// where
+///     cfgenius::cond_tokens!(if macro(is_threaded) { T } else { () }): Send,
+/// {
+///     task
+/// }
+///
+/// spawn_task(std::rc::Rc::new(1));
+/// ```
+///
+/// The same can be done with `cfg` predicates by using [`cond_ty!`](crate::cond_ty) in place of
+/// this macro.
+#[cfg(doc)]
+#[macro_export]
+macro_rules! cond_tokens {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {};
+}
+
+#[cfg(not(doc))]
+#[macro_export]
+macro_rules! cond_tokens {
+    (
+        $(if $($pred:ident)? ($($pred_args:tt)*) {
+            $($yes:tt)*
+        }) else + $(else {
+            $($no:tt)*
+        })?
+    ) => {
+        $crate::cond_tokens! {
+            @__internal_check [$($($pred)? ($($pred_args)*))*]
+            $(
+                if $($pred)? ($($pred_args)*) {
+                    $($yes)*
+                }
+            ) else + $(else {
+                $($no)*
+            })?
+        }
+    };
+
+    // Predicates nested in branches which are never taken are never munched, so `cond!` validates
+    // them up-front. The validator expands to nothing, which can't be emitted in the positions this
+    // macro is used in, so we check the predicates here instead. They are kept in a flat list into
+    // which the operands of each group are spliced. Valid predicates are consumed and anything else
+    // is handed to the validator of `cond!`, which reports the same errors. Predicates which would
+    // expand to attributes are rejected along the way.
+    (@__internal_check [] $($chain:tt)*) => {
+        $crate::__cond! { @__internal_chained_munch $($chain)* }
+    };
+    (@__internal_check [true() $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [false() $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check [$($rest)*] $($chain)* }
+    };
+    (
+        @__internal_check [
+            macro($(!)? $($first:ident)? $(:: $path:ident)* $( => $($args:tt)*)?)
+            $($rest:tt)*
+        ]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! { @__internal_check [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [($($pred:ident)? ($($pred_args:tt)*)) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check [$($pred)? ($($pred_args)*) $($rest)*] $($chain)* }
+    };
+    (@__internal_check [not($($pred:ident)? ($($pred_args:tt)*)) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check [$($pred)? ($($pred_args)*) $($rest)*] $($chain)* }
+    };
+    (@__internal_check [all($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [any($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [majority($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [one_of($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [nor($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [none_of($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [nand($($preds:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check_list [$($preds)*] [$($rest)*] $($chain)* }
+    };
+    (
+        @__internal_check [
+            xor(
+                $($left:ident)? ($($left_args:tt)*),
+                $($right:ident)? ($($right_args:tt)*) $(,)?
+            )
+            $($rest:tt)*
+        ]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! {
+            @__internal_check [$($left)? ($($left_args)*) $($right)? ($($right_args)*) $($rest)*]
+            $($chain)*
+        }
+    };
+    (
+        @__internal_check [
+            implies(
+                $($left:ident)? ($($left_args:tt)*),
+                $($right:ident)? ($($right_args:tt)*) $(,)?
+            )
+            $($rest:tt)*
+        ]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! {
+            @__internal_check [$($left)? ($($left_args)*) $($right)? ($($right_args)*) $($rest)*]
+            $($chain)*
+        }
+    };
+    (
+        @__internal_check [
+            iff(
+                $($left:ident)? ($($left_args:tt)*),
+                $($right:ident)? ($($right_args:tt)*) $(,)?
+            )
+            $($rest:tt)*
+        ]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! {
+            @__internal_check [$($left)? ($($left_args)*) $($right)? ($($right_args)*) $($rest)*]
+            $($chain)*
+        }
+    };
+    (
+        @__internal_check [at_least($count:literal $(, $($preds:tt)*)?) $($rest:tt)*]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! { @__internal_check_list [$($($preds)*)?] [$($rest)*] $($chain)* }
+    };
+    (
+        @__internal_check [at_most($count:literal $(, $($preds:tt)*)?) $($rest:tt)*]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! { @__internal_check_list [$($($preds)*)?] [$($rest)*] $($chain)* }
+    };
+    (
+        @__internal_check [exactly($count:literal $(, $($preds:tt)*)?) $($rest:tt)*]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! { @__internal_check_list [$($($preds)*)?] [$($rest)*] $($chain)* }
+    };
+    (@__internal_check [cfg($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported cfg }
+    };
+    (@__internal_check [cfg_not($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported cfg_not }
+    };
+    (@__internal_check [cfg_eq($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported cfg_eq }
+    };
+    (@__internal_check [feature($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported feature }
+    };
+    (@__internal_check [target_os($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported target_os }
+    };
+    (@__internal_check [rustc_at_least($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported rustc_at_least }
+    };
+    (@__internal_check [ptr_width_eq($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported ptr_width_eq }
+    };
+    (@__internal_check [ptr_width_at_least($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported ptr_width_at_least }
+    };
+    (@__internal_check [between($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported between }
+    };
+    // The predicates produced by spliced lists cannot be checked ahead of time.
+    (@__internal_check [all_spliced($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported all_spliced }
+    };
+    (@__internal_check [any_spliced($($args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_unsupported any_spliced }
+    };
+    (@__internal_check [$($pred:ident)? ($($pred_args:tt)*) $($rest:tt)*] $($chain:tt)*) => {
+        $crate::__cond! { @__internal_validate $($pred)? ($($pred_args)*) }
+    };
+    (@__internal_check_list [] [$($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! { @__internal_check [$($rest)*] $($chain)* }
+    };
+    (
+        @__internal_check_list [$($($pred:ident)? ($($pred_args:tt)*)),+ $(,)?] [$($rest:tt)*]
+        $($chain:tt)*
+    ) => {
+        $crate::cond_tokens! {
+            @__internal_check [$($($pred)? ($($pred_args)*))+ $($rest)*]
+            $($chain)*
+        }
+    };
+    // The validator expands each operand separately, so only the first malformed operand is handed
+    // to it.
+    (@__internal_check_list [$($pred:ident)? ($($pred_args:tt)*), $($preds:tt)*] [$($rest:tt)*] $($chain:tt)*) => {
+        $crate::cond_tokens! {
+            @__internal_check_list [$($preds)*] [$($pred)? ($($pred_args)*) $($rest)*]
+            $($chain)*
+        }
+    };
+    (@__internal_check_list [$($preds:tt)*] $rest:tt $($chain:tt)*) => {
+        $crate::__cond! { @__internal_validate_list $($preds)* }
+    };
+    (@__internal_unsupported $name:ident) => {
+        ::core::compile_error!(::core::concat!(
+            "`cond_tokens!` cannot evaluate `",
+            ::core::stringify!($name),
+            "` predicates; `define!` a variable with it and use `macro(...)` instead",
+        ))
+    };
+}
+
+/// Asserts at compile time that a predicate holds.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// static_assert_cfg!(<predicate>);
+/// ```
+///
+/// ...or, if you want to provide your own error message:
+///
+/// ```plain_text
+/// static_assert_cfg!(<predicate>, "<message>");
+/// ```
+///
+/// This can be used in both item and statement position. If the predicate is falsy, compilation
+/// fails with the provided message or, if none was provided, a message containing the predicate.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::define!(is_supported = any(cfg(unix), cfg(windows), true()));
+///
+/// cfgenius::static_assert_cfg!(macro(is_supported), "backend not supported on this target");
+///
+/// cfgenius::define! {
+///     uses_openssl = false();
+///     uses_rustls = any(cfg(unix), cfg(windows), true());
+/// }
+///
+/// cfgenius::static_assert_cfg!(
+///     one_of(macro(uses_openssl), macro(uses_rustls)),
+///     "exactly one TLS backend must be selected",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// cfgenius::static_assert_cfg!(all(true(), false()));
+/// ```
+#[macro_export]
+macro_rules! static_assert_cfg {
+    ($($pred:ident)? ($($pred_args:tt)*) $(,)?) => {
+        $crate::static_assert_cfg!(
+            $($pred)? ($($pred_args)*),
+            ::core::concat!(
+                "static assertion failed: `",
+                ::core::stringify!($($pred)?($($pred_args)*)),
+                "`",
+            ),
+        );
+    };
+    ($($pred:ident)? ($($pred_args:tt)*), $message:expr $(,)?) => {
+        $crate::cond! {
+            if $($pred)? ($($pred_args)*) {
+            } else {
+                ::core::compile_error!($message);
+            }
+        }
+    };
+}
+
+/// Emits a compile-time warning if a predicate holds.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// warn_cfg!(<predicate>, "<message>");
+/// ```
+///
+/// This can be used in both item and statement position. Rust has no way for macros to emit
+/// warnings directly so, when the predicate is truthy, this expands to an anonymous constant which
+/// uses a local `#[deprecated(note = "<message>")]` item. The warning is therefore reported by the
+/// `deprecated` lint as a use of a deprecated unit struct named `CfgWarning` and can be silenced or
+/// turned into an error like any other use of that lint. When the predicate is falsy, nothing is
+/// emitted.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```
+/// cfgenius::warn_cfg!(
+///     all(feature("legacy-backend"), not(feature("std"))),
+///     "the legacy backend is deprecated without `std`",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// #![deny(deprecated)]
+///
+/// cfgenius::warn_cfg!(true(), "this is always reported");
+/// ```
+#[macro_export]
+macro_rules! warn_cfg {
+    ($($pred:ident)? ($($pred_args:tt)*), $message:literal $(,)?) => {
+        $crate::cond! {
+            if $($pred)? ($($pred_args)*) {
+                const _: () = {
+                    #[deprecated(note = $message)]
+                    struct CfgWarning;
+
+                    let _ = CfgWarning;
+                };
+            }
+        }
+    };
+}
+
+/// Reports whether a predicate holds on the current target as a compile error.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// cond_debug!(<predicate>);
+/// ```
+///
+/// This is a debugging aid which can be temporarily inserted in item or statement position to
+/// find out how a predicate resolves without running any code. Since the predicate is evaluated
+/// by [`cond!`](crate::cond), the result is exactly the one `cond!` would observe. Compilation
+/// always fails with an error of the form ``cfgenius predicate `<predicate>` is truthy`` or
+/// ``cfgenius predicate `<predicate>` is falsy`` so the macro must be removed again afterwards.
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Example
+///
+/// ```compile_fail
+/// cfgenius::define!(is_supported = any(cfg(unix), cfg(windows)));
+///
+/// // error: cfgenius predicate `...` is truthy
+/// cfgenius::cond_debug!(all(macro(is_supported), not(cfg(miri))));
+/// ```
+#[macro_export]
+macro_rules! cond_debug {
+    ($($pred:ident)? ($($pred_args:tt)*) $(,)?) => {
+        $crate::cond! {
+            if $($pred)? ($($pred_args)*) {
+                ::core::compile_error!(::core::concat!(
+                    "cfgenius predicate `",
+                    ::core::stringify!($($pred)?($($pred_args)*)),
+                    "` is truthy",
+                ));
+            } else {
+                ::core::compile_error!(::core::concat!(
+                    "cfgenius predicate `",
+                    ::core::stringify!($($pred)?($($pred_args)*)),
+                    "` is falsy",
+                ));
+            }
+        }
+    };
+}
+
+/// A conditional-compilation variable that always resolves to `true`.
+///
+/// Note that you can equivalently use the `true()` predicate inside `cfgenius` predicates.
+///
+/// Arguments passed through `macro(<path> => <arguments>)` are accepted and ignored so that
+/// parametrized variables can delegate to this macro:
+///
+/// ```
+/// // Every width is supported on this target.
+/// macro_rules! supports_width {
+///     (args { $($args:tt)* } yes { $($yes:tt)* } no { $($no:tt)* }) => {
+///         cfgenius::truthy! { args { $($args)* } yes { $($yes)* } no { $($no)* } }
+///     };
+/// }
+///
+/// const A: u32 = cfgenius::truthy!(args { 1, 2 } yes { 1 } no { 2 });
+/// const B: bool = cfgenius::cond_expr!(macro(supports_width => 64));
+/// #
+/// # assert_eq!(A, 1);
+/// # assert!(B);
+/// ```
+#[macro_export]
+macro_rules! truthy {
+    ($(args { $($args:tt)* })? yes { $($yes:tt)* } no { $($no:tt)* }) => { $($yes)* };
+}
+
+/// A conditional-compilation variable that always resolves to `false`.
+///
+/// Note that you can equivalently use the `false()` predicate inside `cfgenius` predicates.
+///
+/// Like with [`truthy!`](crate::truthy), arguments are accepted and ignored:
+///
+/// ```
+/// const B: u32 = cfgenius::falsy!(args { 1, 2 } yes { 1 } no { 2 });
+/// const C: bool = cfgenius::cond_expr!(macro(cfgenius::falsy => "ignored"));
+/// #
+/// # assert_eq!(B, 2);
+/// # assert!(!C);
+/// ```
+#[macro_export]
+macro_rules! falsy {
+    ($(args { $($args:tt)* })? yes { $($yes:tt)* } no { $($no:tt)* }) => { $($no)* };
+}
+
+/// Defines a zero or more conditional-compilation variables which evaluate to the provided `cfgenius`
+/// predicate.
+///
+/// These merely desugar to `use` items of [`truthy!`](crate::truthy) and [`falsy!`](crate::falsy).
+/// Since neither of those macros refer back to `cfgenius`, crates which consume these variables
+/// without evaluating any `cfgenius` predicates themselves, e.g. by expanding them directly, do not
+/// need to depend on `cfgenius`.
+///
+/// ## Syntax
+///
+/// ```plain_text
+/// define! {
+///     <attributes> <visibility> <name> = <predicate>
+/// }
+/// ```
+///
+/// ...or, if you want to define more than one predicate:
+///
+/// ```plain_text
+/// define! {
+///     <attributes 1> <visibility 1> <name 1> = <predicate 1>;
+///     <attributes 2> <visibility 2> <name 2> = <predicate 2>;
+///     // ...
+///     <attributes N> <visibility N> <name N> = <predicate N> // <-- the semicolon is optional.
+/// }
+/// ```
+///
+/// Each definition can be preceded by attributes, including doc comments, which are forwarded to
+/// the generated items:
+///
+/// ```
+/// cfgenius::define! {
+///     /// Whether the fast path is available on this target.
+///     pub is_supported = any(cfg(unix), cfg(windows));
+///
+///     #[allow(unused_imports)]
+///     is_unused = false();
+/// }
+/// #
+/// # assert_eq!(cfgenius::cond_expr!(macro(is_supported)), cfg!(any(unix, windows)));
+/// ```
+///
+/// See the [predicates](index.html#predicates) section of the crate documentation for more
+/// information about the predicate grammar.
+///
+/// ## Parameters
+///
+/// Variables can also take parameters, which are substituted into the predicate when the variable
+/// is evaluated using the `macro(<path to macro> => <macro arguments>)` predicate:
+///
+/// ```plain_text
+/// define! {
+///     <visibility> <name>($<param 1>, $<param 2>, ...) = <predicate referencing $<param N>>
+/// }
+/// ```
+///
+/// Each argument must be a single token tree. Unlike regular variables, parametrized variables
+/// desugar to a `macro_rules!` definition. Since `macro_rules!` macros cannot be re-exported
+/// publicly, `pub` parametrized variables are instead annotated with `#[macro_export]`. Other
+/// crates can then access them from the root of the defining crate rather than the module in which
+/// they were defined while the defining crate itself can only refer to them by name after their
+/// definition, like any other `macro_rules!` macro.
+///
+/// ```
+/// cfgenius::define! {
+///     pub(crate) has_target_os($os) = cfg(target_os = $os);
+///     pub(crate) has_any_target_os($first, $second) = any(
+///         macro(has_target_os => $first),
+///         macro(has_target_os => $second),
+///     );
+/// }
+///
+/// cfgenius::cond! {
+///     if macro(has_any_target_os => "linux", "android") {
+///         fn backend() -> &'static str { "linux" }
+///     } else {
+///         fn backend() -> &'static str { "fallback" }
+///     }
+/// }
+/// #
+/// # assert_eq!(backend() == "linux", cfg!(any(target_os = "linux", target_os = "android")));
+/// ```
+///
+/// ## Constants
+///
+/// Regular variables can additionally be exposed as a boolean constant with the same attributes and
+/// visibility, which saves going through [`cond_expr!`](crate::cond_expr) whenever the result is
+/// needed at runtime:
+///
+/// ```plain_text
+/// define! {
+///     <attributes> <visibility> <name> as <constant name> = <predicate>
+/// }
+/// ```
+///
+/// The constant is defined as `cond_expr!(macro(<name>))` so the predicate is only evaluated once.
+///
+/// ```
+/// cfgenius::define! {
+///     /// Whether the target is 64-bit.
+///     pub is_64_bit as IS_64_BIT = ptr_width_eq(64);
+///     pub(crate) is_unix as IS_UNIX = cfg(unix);
+/// }
+///
+/// cfgenius::cond! {
+///     if macro(is_64_bit) {
+///         type Word = u64;
+///     } else {
+///         type Word = u32;
+///     }
+/// }
+///
+/// assert_eq!(IS_64_BIT, cfg!(target_pointer_width = "64"));
+/// assert_eq!(IS_UNIX, cfg!(unix));
+/// assert_eq!(IS_64_BIT, std::mem::size_of::<Word>() == 8);
+/// ```
+///
+/// ## Aliases
+///
+/// A variable defined elsewhere, e.g. in another crate, can be re-exposed under a new name by
+/// aliasing it:
+///
+/// ```plain_text
+/// define! {
+///     <attributes> <visibility> <name> = alias <path to variable>
+/// }
+/// ```
+///
+/// Unlike `<name> = macro(<path to variable>)`, which evaluates the predicate `macro(...)` and
+/// defines a new variable from its result, an alias merely desugars to
+/// `<visibility> use <path to variable> as <name>;`. The aliased variable is therefore not
+/// evaluated again and, if it is parametrized, the alias accepts the same arguments. An alias can
+/// be exposed as a constant using `as <constant name>` like any other variable, as long as it does
+/// not take parameters.
+///
+/// ```
+/// mod crate_1 {
+///     cfgenius::define! {
+///         pub is_supported = any(cfg(unix), cfg(windows));
+///         pub(crate) has_target_os($os) = cfg(target_os = $os);
+///     }
+/// }
+///
+/// cfgenius::define! {
+///     /// Whether `crate_1` supports this target.
+///     pub supports_crate_1 as SUPPORTS_CRATE_1 = alias crate_1::is_supported;
+///     pub(crate) is_os = alias self::crate_1::has_target_os;
+///
+///     // The predicate form works for plain variables but cannot forward arguments.
+///     pub(crate) is_supported_copy = macro(crate_1::is_supported);
+/// }
+/// #
+/// # fn main() {
+/// #     assert_eq!(SUPPORTS_CRATE_1, cfg!(any(unix, windows)));
+/// #     assert_eq!(
+/// #         cfgenius::cond_expr!(macro(is_supported_copy)),
+/// #         cfgenius::cond_expr!(macro(supports_crate_1)),
+/// #     );
+/// #     assert_eq!(cfgenius::cond_expr!(macro(is_os => "linux")), cfg!(target_os = "linux"));
+/// # }
+/// ```
+///
+/// ## Introspection
+///
+/// The names of the variables defined by a `define!` invocation can additionally be collected
+/// into a constant, e.g. to report the configuration of a crate in diagnostics:
+///
+/// ```plain_text
+/// define! {
+///     @introspect <attributes> <visibility> <constant name> {
+///         <definitions>
+///     }
+/// }
+/// ```
+///
+/// The definitions are expanded as usual and the constant is defined as a `&[&str]` holding the
+/// name of every variable in the order in which they were defined.
+///
+/// ```
+/// cfgenius::define! {
+///     @introspect
+///     /// The variables describing this crate's configuration.
+///     pub CONFIG_VARS {
+///         pub is_unix = cfg(unix);
+///         pub(crate) is_64_bit as IS_64_BIT = ptr_width_eq(64);
+///         has_target_os($os) = cfg(target_os = $os);
+///     }
+/// }
+///
+/// assert_eq!(CONFIG_VARS, ["is_unix", "is_64_bit", "has_target_os"]);
+/// assert_eq!(IS_64_BIT, cfg!(target_pointer_width = "64"));
+/// #
+/// # cfgenius::define! {
+/// #     @introspect MANY_VARS {
+/// #         v0 = cfg(unix); v1 = cfg(unix); v2 = cfg(unix); v3 = cfg(unix);
+/// #         v4 = cfg(unix); v5 = cfg(unix); v6 = cfg(unix); v7 = cfg(unix);
+/// #         v8 = cfg(unix); v9 = cfg(unix); v10 = cfg(unix); v11 = cfg(unix);
+/// #         v12 = cfg(unix); v13 = cfg(unix); v14 = cfg(unix); v15 = cfg(unix);
+/// #         v16 = cfg(unix); v17 = cfg(unix); v18 = cfg(unix); v19 = cfg(unix);
+/// #         v20 = cfg(unix); v21 = cfg(unix); v22 = cfg(unix); v23 = cfg(unix);
+/// #         v24 = cfg(unix); v25 = cfg(unix); v26 = cfg(unix); v27 = cfg(unix);
+/// #         v28 = cfg(unix); v29 = cfg(unix); v30 = cfg(unix); v31 = cfg(unix);
+/// #         v32 = cfg(unix); v33 = cfg(unix); v34 = cfg(unix); v35 = cfg(unix);
+/// #         v36 = cfg(unix); v37 = cfg(unix); v38 = cfg(unix); v39 = cfg(unix);
+/// #         aliased = alias v0;
+/// #     }
+/// # }
+/// #
+/// # assert_eq!(MANY_VARS.len(), 41);
+/// ```
+///
+/// ## Registries
+///
+/// Referring to a variable which doesn't exist is a compile error, even in branches which are never
+/// taken. Crates which add variables over time can therefore additionally define a registry, i.e. a
+/// parametrized variable recording which of their variables exist:
+///
+/// ```plain_text
+/// define! {
+///     @registry <attributes> <visibility> <registry name> {
+///         <definitions>
+///     }
+/// }
+/// ```
+///
+/// The definitions are expanded as usual and `macro(<registry name> => <name>)` is truthy if and
+/// only if `<name>` was defined by them. Since [`all`](crate::cond) stops evaluating its operands
+/// as soon as a `macro(...)` predicate is falsy, a variable which may not exist can then be checked
+/// safely with `all(macro(<registry> => <name>), macro(<path to variable>))`. Like other `pub`
+/// parametrized variables, `pub` registries are `#[macro_export]`ed from the root of the defining
+/// crate.
+///
+/// ```
+/// mod crate_1 {
+///     cfgenius::define! {
+///         @registry
+///         /// The variables defined by this module.
+///         pub(crate) is_defined {
+///             pub(crate) is_unix = cfg(unix);
+///         }
+///     }
+/// }
+///
+/// cfgenius::cond! {
+///     if all(macro(crate_1::is_defined => is_fast), macro(crate_1::is_fast)) {
+///         const FAST: bool = true;
+///     } else {
+///         const FAST: bool = false;
+///     }
+/// }
+/// #
+/// # fn main() {
+/// #     assert!(!FAST);
+/// #     assert!(cfgenius::cond_expr!(macro(crate_1::is_defined => is_unix)));
+/// #     assert_eq!(
+/// #         cfgenius::cond_expr!(all(macro(crate_1::is_defined => is_unix), macro(crate_1::is_unix))),
+/// #         cfg!(unix),
+/// #     );
+/// #     assert!(cfgenius::cond_expr!(macro(crate_2::is_defined => v39)));
+/// # }
+/// #
+/// # mod crate_2 {
+/// #     cfgenius::define! {
+/// #         @registry pub(crate) is_defined {
+/// #             v0 = cfg(unix); v1 = cfg(unix); v2 = cfg(unix); v3 = cfg(unix);
+/// #             v4 = cfg(unix); v5 = cfg(unix); v6 = cfg(unix); v7 = cfg(unix);
+/// #             v8 = cfg(unix); v9 = cfg(unix); v10 = cfg(unix); v11 = cfg(unix);
+/// #             v12 = cfg(unix); v13 = cfg(unix); v14 = cfg(unix); v15 = cfg(unix);
+/// #             v16 = cfg(unix); v17 = cfg(unix); v18 = cfg(unix); v19 = cfg(unix);
+/// #             v20 = cfg(unix); v21 = cfg(unix); v22 = cfg(unix); v23 = cfg(unix);
+/// #             v24 = cfg(unix); v25 = cfg(unix); v26 = cfg(unix); v27 = cfg(unix);
+/// #             v28 = cfg(unix); v29 = cfg(unix); v30 = cfg(unix); v31 = cfg(unix);
+/// #             v32 = cfg(unix); v33 = cfg(unix); v34 = cfg(unix); v35 = cfg(unix);
+/// #             v36 = cfg(unix); v37 = cfg(unix); v38 = cfg(unix); v39 = cfg(unix);
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// ## Priority Lists
+///
+/// A variable which should follow the first of several sources which is defined doesn't need a
+/// dedicated predicate. Note that `any(...)` on its own is decided by its first *truthy* operand,
+/// so a source which is defined but falsy would fall through to the next one. Instead, each source
+/// is checked with a [registry](#registries) and paired with its own value, and every later source
+/// only applies if the ones before it are not defined:
+///
+/// ```
+/// mod user_config {
+///     cfgenius::define! {
+///         @registry pub(crate) is_defined {
+///             pub(crate) prefers_threads = false();
+///         }
+///     }
+/// }
+///
+/// cfgenius::define! {
+///     pub(crate) auto_backend = cfg(all());
+///
+///     pub(crate) use_threads = any(
+///         all(
+///             macro(user_config::is_defined => prefers_threads),
+///             macro(user_config::prefers_threads),
+///         ),
+///         all(
+///             not(macro(user_config::is_defined => prefers_threads)),
+///             macro(auto_backend),
+///         ),
+///     );
+/// }
+/// #
+/// # fn main() {
+/// #     assert!(!cfgenius::cond_expr!(macro(use_threads)));
+/// # }
+/// ```
+///
+/// Since `all(...)` stops at its first falsy `macro(...)` operand, the user's variable is never
+/// expanded if it isn't defined. Here, it is defined, so `use_threads` follows it and is falsy even
+/// though `auto_backend` is truthy.
+///
+/// To select a value rather than a truthiness by priority, use
+/// [`select_first!`](crate::select_first).
+///
+/// ## Namespaces
+///
+/// Since every variable is an item named after itself, variables defined by different crates can
+/// collide when they are imported into the same scope. To avoid this, the definitions can be
+/// placed in a module generated by the macro:
+///
+/// ```plain_text
+/// define! {
+///     <attributes> <visibility> in <module name> {
+///         <definitions>
+///     }
+/// }
+/// ```
+///
+/// The module glob-imports its parent so predicates can keep referring to other variables by the
+/// same paths as before. Visibilities are relative to the generated module, meaning that `pub(super)`
+/// exports a variable to the module's parent rather than its grandparent. Because `pub` parametrized
+/// variables are exported from the root of the crate, they are not affected by the namespace and
+/// the defining crate can still refer to them by name after the module.
+///
+/// ```
+/// cfgenius::define!(is_unix = cfg(unix));
+///
+/// cfgenius::define! {
+///     /// Variables describing the backend in use.
+///     pub in backend {
+///         pub is_posix = any(macro(is_unix), target_os("wasi"));
+///         pub(crate) is_fallback = not(macro(is_posix));
+///     }
+/// }
+///
+/// cfgenius::cond! {
+///     if macro(backend::is_posix) {
+///         fn backend_name() -> &'static str { "posix" }
+///     } else {
+///         fn backend_name() -> &'static str { "fallback" }
+///     }
+/// }
+/// #
+/// # fn main() {
+/// #     assert_eq!(backend_name() == "posix", cfg!(any(unix, target_os = "wasi")));
+/// #     assert!(cfgenius::cond_expr!(xor(
+/// #         macro(backend::is_posix),
+/// #         macro(backend::is_fallback),
+/// #     )));
+/// # }
+/// ```
+///
+/// See also the [custom variable](index.html#custom-variables) section of the crate documentation
+/// for information how to define more complex variables, potentially with arguments.
 #[cfg(doc)]
 #[macro_export]
-macro_rules! cond {
+macro_rules! define {
     (
-        $(if $pred:ident ($($pred_args:tt)*) {
-            $($yes:tt)*
-        }) else + $(else {
-            $($no:tt)*
-        })?
+        $(
+            $(#[$attr:meta])*
+            $vis:vis $name:ident $(($($params:tt)*))? $(as $const_name:ident)?
+                = $(alias $(::)? $path_first:ident $(:: $path_rest:ident)*)?
+                    $($($pred:ident)? ($($pred_args:tt)*))?
+        );* $(;)?
+    ) => {};
+    (
+        $(#[$mod_attr:meta])*
+        $mod_vis:vis in $module:ident {
+            $(
+                $(#[$attr:meta])*
+                $vis:vis $name:ident $(($($params:tt)*))? $(as $const_name:ident)?
+                    = $(alias $(::)? $path_first:ident $(:: $path_rest:ident)*)?
+                        $($($pred:ident)? ($($pred_args:tt)*))?
+            );* $(;)?
+        }
+    ) => {};
+    (
+        @registry $(#[$registry_attr:meta])* $registry_vis:vis $registry_name:ident {
+            $(
+                $(#[$attr:meta])*
+                $vis:vis $name:ident $(($($params:tt)*))? $(as $const_name:ident)?
+                    = $(alias $(::)? $path_first:ident $(:: $path_rest:ident)*)?
+                        $($($pred:ident)? ($($pred_args:tt)*))?
+            );* $(;)?
+        }
+    ) => {};
+    (
+        @introspect $(#[$list_attr:meta])* $list_vis:vis $list_name:ident {
+            $(
+                $(#[$attr:meta])*
+                $vis:vis $name:ident $(($($params:tt)*))? $(as $const_name:ident)?
+                    = $(alias $(::)? $path_first:ident $(:: $path_rest:ident)*)?
+                        $($($pred:ident)? ($($pred_args:tt)*))?
+            );* $(;)?
+        }
     ) => {};
 }
 
 #[cfg(not(doc))]
 #[macro_export]
-macro_rules! cond {
-    // We begin by implementing `cond!` for one level of `if ... { ... } else { ... }`.
-
-    // true
+macro_rules! define {
+    // Because `pub` parametrized variables must be handled differently from other visibilities, we
+    // have to munch through the definitions one at a time rather than matching them all at once.
+    (@__internal_munch) => {};
     (
-        @__internal_single_munch
-        if true() {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
-        }
+        @__internal_munch
+        $(#[$attr:meta])*
+        pub $name:ident ($($params:tt)*) = $($pred:ident)? ($($pred_args:tt)*)
+        $(; $($rest:tt)*)?
     ) => {
-        $($yes)*
-    };
+        $crate::define! {
+            @__internal_parametrized [$(#[$attr])* #[macro_export]] []
+            $name ($($params)*) = $($pred)? ($($pred_args)*)
+        }
 
-    // false
+        $crate::define! { @__internal_munch $($($rest)*)? }
+    };
     (
-        @__internal_single_munch
-        if false() {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
-        }
+        @__internal_munch
+        $(#[$attr:meta])*
+        $vis:vis $name:ident ($($params:tt)*) = $($pred:ident)? ($($pred_args:tt)*)
+        $(; $($rest:tt)*)?
     ) => {
-        $($no)*
+        $crate::define! {
+            @__internal_parametrized [$(#[$attr])*] [$(#[$attr])* $vis use $name;]
+            $name ($($params)*) = $($pred)? ($($pred_args)*)
+        }
+
+        $crate::define! { @__internal_munch $($($rest)*)? }
     };
+    // Aliases re-export the aliased macro directly so that its predicate is not evaluated a second
+    // time and its parameters, if any, are kept.
+    (
+        @__internal_munch
+        $(#[$attr:meta])*
+        $vis:vis $name:ident = alias $path:path
+        $(; $($rest:tt)*)?
+    ) => {
+        $(#[$attr])*
+        $vis use $path as $name;
 
-    // cfg
-    (@__internal_id $($id:tt)*) => { $($id)* };
+        $crate::define! { @__internal_munch $($($rest)*)? }
+    };
     (
-        @__internal_single_munch
-        if cfg($($args:tt)*) {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
-        }
+        @__internal_munch
+        $(#[$attr:meta])*
+        $vis:vis $name:ident as $const_name:ident = alias $path:path
+        $(; $($rest:tt)*)?
     ) => {
-        #[cfg($($args)*)] $crate::cond! { @__internal_id $($yes)* }
-        #[cfg(not($($args)*))] $crate::cond! { @__internal_id $($no)* }
+        $crate::define! {
+            @__internal_munch $(#[$attr])* $vis $name = alias $path
+        }
+
+        $(#[$attr])*
+        $vis const $const_name: bool = $crate::cond_expr!(macro($name));
+
+        $crate::define! { @__internal_munch $($($rest)*)? }
     };
+    (
+        @__internal_munch
+        $(#[$attr:meta])*
+        $vis:vis $name:ident as $const_name:ident = $($pred:ident)? ($($pred_args:tt)*)
+        $(; $($rest:tt)*)?
+    ) => {
+        $crate::define! { @__internal_munch $(#[$attr])* $vis $name = $($pred)? ($($pred_args)*) }
 
-    // not
+        $(#[$attr])*
+        $vis const $const_name: bool = $crate::cond_expr!(macro($name));
+
+        $crate::define! { @__internal_munch $($($rest)*)? }
+    };
     (
-        @__internal_single_munch
-        if not($pred:ident ($($pred_args:tt)*)) {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
-        }
+        @__internal_munch
+        $(#[$attr:meta])*
+        $vis:vis $name:ident = $($pred:ident)? ($($pred_args:tt)*)
+        $(; $($rest:tt)*)?
     ) => {
         $crate::cond! {
-            if $pred($($pred_args)*) {
-                $($no)*
+            if $($pred)?($($pred_args)*) {
+                $(#[$attr])*
+                $vis use $crate::truthy as $name;
             } else {
-                $($yes)*
+                $(#[$attr])*
+                $vis use $crate::falsy as $name;
             }
         }
+
+        $crate::define! { @__internal_munch $($($rest)*)? }
     };
 
-    // all
+    // Parameters are written as `$name` by the user so their `$` tokens can be reused to write the
+    // metavariables of the generated macro.
     (
-        @__internal_single_munch
-        if all(
-            $first_pred:ident($($first_args:tt)*)
-            $(, $($rest:tt)*)?
-        ) {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
-        }
+        @__internal_parametrized [$($attrs:tt)*] [$($reexport:tt)*]
+        $name:ident ($d:tt $first_param:ident $(, $_d:tt $param:ident)* $(,)?)
+        = $($pred:ident)? ($($pred_args:tt)*)
     ) => {
-        $crate::cond! {
-            @__internal_single_munch
-            if $first_pred($($first_args)*) {
+        $($attrs)*
+        macro_rules! $name {
+            (
+                args { $d $first_param:tt $(, $d $param:tt)* $d(,)? }
+                yes { $d($d yes:tt)* }
+                no { $d($d no:tt)* }
+            ) => {
                 $crate::cond! {
-                    @__internal_single_munch
-                    if all($($($rest)*)?) {
-                        $($yes)*
+                    if $($pred)? ($($pred_args)*) {
+                        $d($d yes)*
                     } else {
-                        $($no)*
+                        $d($d no)*
                     }
                 }
-            } else {
-                $($no)*
-            }
+            };
         }
+
+        $($reexport)*
     };
     (
-        @__internal_single_munch
-        if all() {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
-        }
-    ) => { $($yes)* };
+        @__internal_parametrized [$($attrs:tt)*] [$($reexport:tt)*]
+        $name:ident ($($params:tt)*) = $($pred:ident)? ($($pred_args:tt)*)
+    ) => {
+        ::core::compile_error!(::core::concat!(
+            "expected one or more parameters of the form `$name`, found `",
+            ::core::stringify!($($params)*),
+            "`",
+        ));
+    };
+    (@__internal_munch $($tokens:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "expected a variable definition of the form `<visibility> <name> = <predicate>`, found `",
+            ::core::stringify!($($tokens)*),
+            "`",
+        ));
+    };
 
-    // any
+    // The names are collected separately by matching all of the definitions at once. Predicates
+    // and aliased paths are only matched loosely since `@__internal_munch` already checks them.
     (
-        @__internal_single_munch
-        if any($first_pred:ident($($first_args:tt)*) $(, $($rest:tt)*)?) {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
+        @introspect $(#[$list_attr:meta])* $list_vis:vis $list_name:ident {
+            $($definitions:tt)*
         }
     ) => {
-        $crate::cond! {
-            @__internal_single_munch
-            if $first_pred($($first_args)*) {
-                $($yes)*
-            } else {
-                $crate::cond! {
-                    @__internal_single_munch
-                    if any($($($rest)*)?) {
-                        $($yes)*
-                    } else {
-                        $($no)*
-                    }
-                }
-            }
+        $crate::define! { @__internal_munch $($definitions)* }
+        $crate::define! {
+            @__internal_names [@__internal_introspect $(#[$list_attr])* $list_vis $list_name]
+            { $($definitions)* }
         }
     };
     (
-        @__internal_single_munch
-        if any() {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
+        @registry $(#[$registry_attr:meta])* pub $registry_name:ident {
+            $($definitions:tt)*
         }
-    ) => { $($no)* };
-
-    // macro
+    ) => {
+        $crate::define! { @__internal_munch $($definitions)* }
+        $crate::define! {
+            @__internal_names [
+                @__internal_registry ($) [$(#[$registry_attr])* #[macro_export]] []
+                $registry_name
+            ]
+            { $($definitions)* }
+        }
+    };
     (
-        @__internal_single_munch
-        if macro($path:path $( => $($args:tt)*)?) {
-            $($yes:tt)*
-        } else {
-            $($no:tt)*
+        @registry $(#[$registry_attr:meta])* $registry_vis:vis $registry_name:ident {
+            $($definitions:tt)*
         }
     ) => {
-        $path! { $(args { $($args)* })? yes { $($yes)* } no { $($no)* } }
+        $crate::define! { @__internal_munch $($definitions)* }
+        $crate::define! {
+            @__internal_names [
+                @__internal_registry ($) [$(#[$registry_attr])*]
+                [$(#[$registry_attr])* $registry_vis use $registry_name;]
+                $registry_name
+            ]
+            { $($definitions)* }
+        }
     };
-
-    // Now, we can implement support for an arbitrary chaining of these.
-    // TODO: Validate `cond!` grammar in its entirety, even if the faulty branches are never taken.
-
-    // Because falsy paths are never expanded into the final output, bad macro calls to `cond!` are
-    // ignored in the falsy paths, which is a bit janky. We avoid this scenario by validating the
-    // syntax before munching through it.
     (
-        $(if $pred:ident ($($pred_args:tt)*) {
-            $($yes:tt)*
-        }) else + $(else {
-            $($no:tt)*
-        })?
-    ) => {
-        $crate::cond! {
-            @__internal_chained_munch
+        @__internal_names [$($then:tt)*] {
             $(
-                if $pred($($pred_args)*) {
-                    $($yes)*
-                }
-            ) else + $(else {
-                $($no)*
-            })?
+                $(#[$attr:meta])*
+                $vis:vis $name:ident $(($($params:tt)*))? $(as $const_name:ident)?
+                    = $($value:ident)* $(:: $path:ident)* $(($($pred_args:tt)*))?
+            );* $(;)?
         }
+    ) => {
+        $crate::define! { $($then)* [$($name)*] }
+    };
+    // Malformed definitions are already reported by `@__internal_munch`.
+    (@__internal_names $then:tt $definitions:tt) => {};
+    (
+        @__internal_introspect $(#[$list_attr:meta])* $list_vis:vis $list_name:ident
+        [$($name:ident)*]
+    ) => {
+        $(#[$list_attr])*
+        $list_vis const $list_name: &[&str] = &[$(::core::stringify!($name)),*];
     };
 
+    // Like parametrized variables, registries are `macro_rules!` macros. Their metavariables are
+    // written using the `$` token passed in by the rules above.
     (
-        @__internal_chained_munch
-        if $pred:ident ($($pred_args:tt)*) {
-            $($yes:tt)*
-        } $(else $($rest:tt)*)?
+        @__internal_registry ($d:tt) [$($attrs:tt)*] [$($reexport:tt)*]
+        $registry_name:ident [$($name:ident)*]
     ) => {
-        $crate::cond! {
-            @__internal_single_munch
-            if $pred($($pred_args)*) {
-                $($yes)*
-            } else {
-                $($crate::cond! {
-                    @__internal_chained_munch
-                    $($rest)*
-                })?
-            }
+        $($attrs)*
+        macro_rules! $registry_name {
+            $(
+                (args { $name $d(,)? } yes { $d($d yes:tt)* } no { $d($d no:tt)* }) => {
+                    $d($d yes)*
+                };
+            )*
+            (args { $d($d other:tt)* } yes { $d($d yes:tt)* } no { $d($d no:tt)* }) => {
+                $d($d no)*
+            };
         }
+
+        $($reexport)*
     };
+
     (
-        @__internal_chained_munch
-        { $($rest:tt)* }
+        $(#[$mod_attr:meta])*
+        $mod_vis:vis in $module:ident { $($definitions:tt)* }
     ) => {
-        $($rest)*
+        $(#[$mod_attr])*
+        #[macro_use]
+        $mod_vis mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::define! { @__internal_munch $($definitions)* }
+        }
+    };
+    ($($tokens:tt)*) => {
+        $crate::define! { @__internal_munch $($tokens)* }
     };
 }
 
-/// A conditionally-compiled expression.
+/// Evaluates predicates once into `bool` constants.
 ///
 /// ## Syntax
 ///
 /// ```plain_text
-/// cond_expr! {
-///     if <if predicate> {
-///         // arbitrary tokens forming a `BlockExpression`.
-///     } else if <else-if predicate> {  // There can be zero or more of these.
-///         // arbitrary tokens forming a `BlockExpression`.
-///     } else {                         // This is optional.
-///         // arbitrary tokens forming a `BlockExpression`.
-///     }
+/// define_const! {
+///     <attributes> <visibility> <constant name> = <predicate>;
+///     // There can be zero or more of these.
 /// }
 /// ```
 ///
-/// or, if you just want to evaluate a boolean literal for the predicate, the following alias can
-/// be used instead:
-///
-/// ```plain_text
-/// cond_expr!(<predicate>)
-/// ```
+/// Each definition expands to `<visibility> const <constant name>: bool = cond_expr!(<predicate>);`
+/// so the predicate is only evaluated once, no matter how often the constant is used. Unlike
+/// `<name> as <constant name> = <predicate>` in [`define!`](crate::define), no variable is defined
+/// so the result can't be used in other predicates. Like [`cond_expr!`](crate::cond_expr), the
+/// constants only depend on `core` and can be used in any `const` context.
 ///
 /// See the [predicates](index.html#predicates) section of the crate documentation for more
 /// information about the predicate grammar.
-#[macro_export]
-macro_rules! cond_expr {
-    (
-        $(if $pred:ident ($($pred_args:tt)*) {
-            $($yes:tt)*
-        }) else + $(else {
-            $($no:tt)*
-        })?
-    ) => {'__cond_expr_out: {
-        $crate::cond! {
-            $(if $pred ($($pred_args)*) {
-                break '__cond_expr_out ({ $($yes)* });
-            }) else + $(else {
-                break '__cond_expr_out ({ $($no)* });
-            })?
-        }
-    }};
-    ($pred:ident ($($pred_args:tt)*)) => {
-        $crate::cond_expr! {
-            if $pred($($pred_args)*) {
-                true
-            } else {
-                false
-            }
-        }
-    }
-}
-
-/// A conditional-compilation variable that always resolves to `true`.
-///
-/// Note that you can equivalently use the `true()` predicate inside `cfgenius` predicates.
-#[macro_export]
-macro_rules! truthy {
-    (yes { $($yes:tt)* } no { $($no:tt)* }) => { $($yes)* };
-}
-
-/// A conditional-compilation variable that always resolves to `false`.
 ///
-/// Note that you can equivalently use the `false()` predicate inside `cfgenius` predicates.
-#[macro_export]
-macro_rules! falsy {
-    (yes { $($yes:tt)* } no { $($no:tt)* }) => { $($no)* };
-}
-
-/// Defines a zero or more conditional-compilation variables which evaluate to the provided `cfgenius`
-/// predicate.
-///
-/// These merely desugar to `use` items of [`truthy!`](crate::truthy) and [`falsy!`](crate::falsy).
-///
-/// ## Syntax
+/// ## Example
 ///
-/// ```plain_text
-/// define! {
-///     <visibility> <name> = <predicate>
-/// }
 /// ```
-///
-/// ...or, if you want to define more than one predicate:
-///
-/// ```plain_text
-/// define! {
-///     <visibility 1> <name 1> = <predicate 1>;
-///     <visibility 2> <name 2> = <predicate 2>;
-///     // ...
-///     <visibility N> <name N> = <predicate N> // <-- the semicolon is optional.
+/// cfgenius::define! {
+///     pub is_supported = any(cfg(unix), cfg(windows));
 /// }
-/// ```
 ///
-/// See the [predicates](index.html#predicates) section of the crate documentation for more
-/// information about the predicate grammar.
+/// cfgenius::define_const! {
+///     /// Whether this target is supported.
+///     pub IS_OK = macro(is_supported);
+///     pub(crate) HAS_WIDE_POINTERS = ptr_width_at_least(64);
+///     IS_MIRI = cfg(miri)
+/// }
 ///
-/// See also the [custom variable](index.html#custom-variables) section of the crate documentation
-/// for information how to define more complex variables, potentially with arguments.
+/// const WORD_BYTES: usize = if HAS_WIDE_POINTERS { 8 } else { 4 };
+/// const BUFFER_LEN: usize = if IS_OK && !IS_MIRI { 64 } else { 0 };
+/// static BUFFER: [u8; BUFFER_LEN] = [0; BUFFER_LEN];
+/// #
+/// # assert_eq!(IS_OK, cfg!(any(unix, windows)));
+/// # assert_eq!(WORD_BYTES == 8, cfg!(target_pointer_width = "64"));
+/// # assert_eq!(BUFFER.len() == 64, IS_OK && !cfg!(miri));
+/// ```
 #[macro_export]
-macro_rules! define {
+macro_rules! define_const {
     (
-        $( $vis:vis $name:ident = $pred:ident ($($pred_args:tt)*) );* $(;)?
+        $(
+            $(#[$attr:meta])*
+            $vis:vis $name:ident = $($pred:ident)? ($($pred_args:tt)*)
+        );* $(;)?
     ) => {
         $(
-            $crate::cond! {
-                if $pred($($pred_args)*) {
-                    $vis use $crate::truthy as $name;
-                } else {
-                    $vis use $crate::falsy as $name;
-                }
-            }
+            $(#[$attr])*
+            $vis const $name: bool = $crate::cond_expr!($($pred)? ($($pred_args)*));
         )*
     };
 }
@@ -0,0 +1,67 @@
+//! Compiles each `tests/ui/*.rs` file against the crate and compares the diagnostics `rustc`
+//! reports with the expected `.stderr` file next to it. This checks where errors point, which a
+//! `compile_fail` doctest cannot. Set `CFGENIUS_BLESS=1` to overwrite the expected output.
+
+use std::{env, fs, path::Path, process::Command};
+
+fn rustc() -> Command {
+    Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+#[test]
+fn ui() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let status = rustc()
+        .current_dir(root)
+        .args(["--edition=2021", "--crate-type=rlib", "--crate-name=cfgenius"])
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg("src/lib.rs")
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to build cfgenius for the UI tests");
+
+    let mut cases = fs::read_dir(root.join("tests/ui"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    cases.sort();
+
+    let bless = env::var_os("CFGENIUS_BLESS").is_some();
+    let mut failures = Vec::new();
+
+    for case in cases {
+        let name = case.file_name().unwrap().to_str().unwrap();
+        let output = rustc()
+            .current_dir(root)
+            .args(["--edition=2021", "--error-format=short", "--emit=metadata"])
+            .arg("--out-dir")
+            .arg(&out_dir)
+            .arg("--extern")
+            .arg(format!("cfgenius={}", out_dir.join("libcfgenius.rlib").display()))
+            .arg(format!("tests/ui/{name}"))
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success(), "{name} was expected to fail to compile");
+
+        let actual = String::from_utf8(output.stderr).unwrap();
+        let expected_path = case.with_extension("stderr");
+
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+        } else if fs::read_to_string(&expected_path).ok().as_deref() != Some(&actual) {
+            failures.push(format!("{name}:\n{actual}"));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "unexpected diagnostics (rerun with CFGENIUS_BLESS=1 to update):\n\n{}",
+        failures.join("\n"),
+    );
+}
@@ -0,0 +1,9 @@
+cfgenius::cond! {
+    if all(true(), not(false())) {
+        const VALUE: u32 = "not a number";
+    }
+}
+
+fn main() {
+    let _ = VALUE;
+}